@@ -1,10 +1,24 @@
-use bevy::prelude::*;
+use std::f32::consts;
 
-#[derive(Component)]
+use bevy::{
+    ecs::{
+        entity::{EntityMapper, MapEntities},
+        reflect::ReflectMapEntities,
+    },
+    prelude::*,
+};
+
+use crate::{
+    control::{Action, Bindings, Control, ControlSystems, Devices, Input, InputSource},
+    shape_caster::ShapeCaster,
+};
+
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
 pub struct Spectate;
 
 #[derive(Component, Reflect)]
-#[reflect(Component)]
+#[reflect(Component, MapEntities)]
 pub struct CameraController {
     pub target: Entity,
 }
@@ -15,19 +29,122 @@ impl CameraController {
     }
 }
 
+impl MapEntities for CameraController {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.target = entity_mapper.map_entity(self.target);
+    }
+}
+
+#[derive(Reflect, Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub enum CameraMode {
+    #[default]
+    FirstPerson,
+    OverShoulder,
+    Orbit,
+}
+
+impl CameraMode {
+    pub fn next(self) -> Self {
+        match self {
+            CameraMode::FirstPerson => CameraMode::OverShoulder,
+            CameraMode::OverShoulder => CameraMode::Orbit,
+            CameraMode::Orbit => CameraMode::FirstPerson,
+        }
+    }
+}
+
+#[derive(Component, Reflect, Clone)]
+#[reflect(Component, MapEntities)]
+pub struct CameraBoom {
+    pub mode: CameraMode,
+    pub caster: Entity,
+    pub shoulder: Vec3,
+    pub orbit_distance: f32,
+    pub blend_speed: f32,
+    pub margin: f32,
+    pub min_pitch: f32,
+    pub max_pitch: f32,
+    pub max_yaw: f32,
+    pub pitch: f32,
+    pub yaw: f32,
+    offset: Vec3,
+}
+
+impl CameraBoom {
+    pub fn new(caster: Entity) -> Self {
+        Self {
+            mode: CameraMode::FirstPerson,
+            caster,
+            shoulder: Vec3::new(0.5, 0.2, 2.5),
+            orbit_distance: 5.0,
+            blend_speed: 8.0,
+            margin: 0.1,
+            min_pitch: -consts::FRAC_PI_2,
+            max_pitch: consts::FRAC_PI_2,
+            max_yaw: consts::PI,
+            pitch: 0.0,
+            yaw: 0.0,
+            offset: Vec3::ZERO,
+        }
+    }
+
+    pub fn offset(&self) -> Vec3 {
+        self.offset
+    }
+
+    pub fn target_offset(&self) -> Vec3 {
+        match self.mode {
+            CameraMode::FirstPerson => Vec3::ZERO,
+            CameraMode::OverShoulder => self.shoulder,
+            CameraMode::Orbit => Vec3::Z * self.orbit_distance,
+        }
+    }
+
+    pub fn look(&mut self, looking: Vec2) -> f32 {
+        self.pitch = (self.pitch + looking.y).clamp(self.min_pitch, self.max_pitch);
+
+        if self.mode != CameraMode::Orbit {
+            return looking.x;
+        }
+
+        self.yaw = (self.yaw + looking.x).clamp(-self.max_yaw, self.max_yaw);
+
+        0.0
+    }
+
+    pub fn rotation(&self) -> Quat {
+        Quat::from_euler(EulerRot::YXZ, self.yaw, self.pitch, 0.0)
+    }
+}
+
+impl MapEntities for CameraBoom {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.caster = entity_mapper.map_entity(self.caster);
+    }
+}
+
 #[derive(SystemSet, Hash, Debug, PartialEq, Eq, Clone)]
 pub enum CameraControllerSystems {
     Resolve,
+    Boom,
 }
 
 pub struct CameraControllerPlugin;
 
 impl Plugin for CameraControllerPlugin {
     fn build(&self, app: &mut App) {
-        app.register_type::<CameraController>().add_systems(
-            First,
-            (clean_up, resolve).in_set(CameraControllerSystems::Resolve),
-        );
+        app.register_type::<CameraController>()
+            .register_type::<Spectate>()
+            .register_type::<CameraMode>()
+            .register_type::<CameraBoom>()
+            .add_systems(
+                First,
+                (clean_up, resolve)
+                    .chain()
+                    .in_set(CameraControllerSystems::Resolve),
+            )
+            .add_systems(Update, boom.in_set(CameraControllerSystems::Boom))
+            .add_systems(PreUpdate, cycling.after(ControlSystems));
     }
 }
 
@@ -43,17 +160,23 @@ fn clean_up(removed: RemovedComponents<Spectate>, mut camera_q: CameraQuery) {
     }
 }
 
+type AddedQuery<'world, 'state> = Query<
+    'world,
+    'state,
+    (),
+    (
+        Or<(Added<Spectate>, Added<CameraController>)>,
+        With<Spectate>,
+    ),
+>;
+
 fn resolve(
-    entity_q: Query<
-        &CameraController,
-        (
-            Or<(Added<Spectate>, Added<CameraController>)>,
-            With<Spectate>,
-        ),
-    >,
+    removed: RemovedComponents<Spectate>,
+    added_q: AddedQuery,
+    entity_q: Query<(Entity, &CameraController), With<Spectate>>,
     mut camera_q: CameraQuery,
 ) {
-    if entity_q.is_empty() {
+    if added_q.is_empty() && removed.is_empty() {
         return;
     }
 
@@ -61,9 +184,18 @@ fn resolve(
         camera.is_active = false
     }
 
-    let controller = entity_q
-        .get_single()
-        .expect("only one entity can have a Spectate component");
+    let Some((entity, controller)) = entity_q.iter().min_by_key(|(entity, _)| *entity) else {
+        return;
+    };
+
+    let count = entity_q.iter().count();
+
+    if count > 1 {
+        warn!(
+            "{} entities have a Spectate component, spectating {}",
+            count, entity
+        );
+    }
 
     let mut camera = camera_q
         .get_mut(controller.target)
@@ -71,3 +203,129 @@ fn resolve(
 
     camera.is_active = true;
 }
+
+fn boom(
+    time: Res<Time>,
+    mut entity_q: Query<(&CameraController, &mut CameraBoom)>,
+    mut caster_q: Query<&mut ShapeCaster>,
+    mut camera_q: Query<&mut Transform, With<Camera>>,
+) {
+    for (controller, mut boom) in entity_q.iter_mut() {
+        let blend = 1.0 - (-boom.blend_speed * time.delta_secs()).exp();
+
+        let target = boom.target_offset();
+
+        boom.offset = boom.offset.lerp(target, blend);
+
+        if boom.mode != CameraMode::Orbit {
+            boom.yaw = boom.yaw.lerp(0.0, blend);
+        }
+
+        let mut caster = caster_q.get_mut(boom.caster).unwrap();
+
+        if let Some(result) = caster.result.as_ref() {
+            let reach = (result.distance - boom.margin).max(0.0);
+
+            if boom.offset.length() > reach {
+                boom.offset = boom.offset.normalize_or_zero() * reach;
+            }
+        }
+
+        caster.direction = if target.length() >= boom.offset.length() {
+            target
+        } else {
+            boom.offset
+        };
+
+        let Ok(mut transform) = camera_q.get_mut(controller.target) else {
+            continue;
+        };
+
+        transform.translation = boom.offset;
+    }
+}
+
+fn cycling(mut commands: Commands, devices: Devices, controls: Res<Bindings>) {
+    if controls.just_pressed(Action::CycleSpectate, &devices) {
+        commands.queue(cycle);
+    }
+}
+
+pub fn spectated(world: &mut World) -> Option<Entity> {
+    world
+        .query_filtered::<Entity, (With<Spectate>, With<CameraController>)>()
+        .iter(world)
+        .min()
+}
+
+pub fn possess(world: &mut World, from: Option<Entity>, to: Entity) {
+    let source = match from {
+        Some(from) => {
+            let source = world
+                .get::<Control>(from)
+                .and(world.get::<InputSource>(from).copied());
+
+            if let Ok(mut entity) = world.get_entity_mut(from) {
+                entity.remove::<(Spectate, Control, InputSource)>();
+
+                if source.is_some() {
+                    entity.insert(Input::default());
+                }
+            }
+
+            source
+        }
+        None => Some(InputSource::default()),
+    };
+
+    let mut entity = world.entity_mut(to);
+
+    entity.insert(Spectate);
+
+    if let Some(source) = source {
+        entity.insert((Control, source));
+    }
+}
+
+pub fn cycle(world: &mut World) {
+    let mut entities = world
+        .query_filtered::<Entity, With<CameraController>>()
+        .iter(world)
+        .collect::<Vec<_>>();
+
+    entities.sort();
+
+    let current = spectated(world);
+
+    let next = match current {
+        Some(current) => entities
+            .iter()
+            .find(|entity| **entity > current)
+            .or(entities.first()),
+        None => entities.first(),
+    };
+
+    let Some(next) = next.copied() else {
+        return;
+    };
+
+    if Some(next) == current {
+        return;
+    }
+
+    let others = world
+        .query_filtered::<Entity, With<Spectate>>()
+        .iter(world)
+        .filter(|entity| Some(*entity) != current)
+        .collect::<Vec<_>>();
+
+    for entity in others {
+        world.entity_mut(entity).remove::<Spectate>();
+    }
+
+    if let Some(current) = current {
+        world.entity_mut(current).remove::<Spectate>();
+    }
+
+    world.entity_mut(next).insert(Spectate);
+}
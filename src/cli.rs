@@ -0,0 +1,118 @@
+use std::{error::Error, fmt};
+
+use bevy::window::{MonitorSelection, WindowMode};
+
+use crate::levels::Levels;
+
+pub const USAGE: &str = "usage: rust_game [options]
+
+options:
+    --level <name>      level to start in (default: test_level)
+    --seed <number>     fixed seed for the random number generator
+    --fullscreen        start in borderless fullscreen
+    --windowed          start in a window (default)
+    --debug             enable debug plugins (default in debug builds)
+    --no-debug          disable debug plugins
+    --headless <ticks>  simulate the given number of fixed ticks without a window and exit
+    --help              print this message";
+
+#[derive(Debug)]
+pub enum CliError {
+    Help,
+    UnknownArgument(String),
+    MissingValue(&'static str),
+    InvalidValue { option: &'static str, value: String },
+    UnknownLevel(String),
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CliError::Help => write!(f, "{}", USAGE),
+            CliError::UnknownArgument(argument) => write!(f, "unknown argument `{}`", argument),
+            CliError::MissingValue(option) => write!(f, "`{}` requires a value", option),
+            CliError::InvalidValue { option, value } => {
+                write!(f, "invalid value `{}` for `{}`", value, option)
+            }
+            CliError::UnknownLevel(name) => write!(
+                f,
+                "unknown level `{}`, available levels: {}",
+                name,
+                Levels::default().names().join(", ")
+            ),
+        }
+    }
+}
+
+impl Error for CliError {}
+
+pub struct Options {
+    pub level: String,
+    pub seed: Option<u64>,
+    pub window_mode: WindowMode,
+    pub debug: bool,
+    pub headless: Option<usize>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            level: "test_level".to_owned(),
+            seed: None,
+            window_mode: WindowMode::Windowed,
+            debug: cfg!(debug_assertions),
+            headless: None,
+        }
+    }
+}
+
+impl Options {
+    pub fn parse(mut args: impl Iterator<Item = String>) -> Result<Self, CliError> {
+        let mut options = Self::default();
+
+        while let Some(argument) = args.next() {
+            match argument.as_str() {
+                "--level" => {
+                    let name = value(&mut args, "--level")?;
+
+                    if !Levels::default().contains(&name) {
+                        return Err(CliError::UnknownLevel(name));
+                    }
+
+                    options.level = name;
+                }
+                "--seed" => options.seed = Some(parse(&mut args, "--seed")?),
+                "--fullscreen" => {
+                    options.window_mode =
+                        WindowMode::BorderlessFullscreen(MonitorSelection::Current)
+                }
+                "--windowed" => options.window_mode = WindowMode::Windowed,
+                "--debug" => options.debug = true,
+                "--no-debug" => options.debug = false,
+                "--headless" => options.headless = Some(parse(&mut args, "--headless")?),
+                "--help" | "-h" => return Err(CliError::Help),
+                _ => return Err(CliError::UnknownArgument(argument)),
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+fn value(
+    args: &mut impl Iterator<Item = String>,
+    option: &'static str,
+) -> Result<String, CliError> {
+    args.next().ok_or(CliError::MissingValue(option))
+}
+
+fn parse<T: std::str::FromStr>(
+    args: &mut impl Iterator<Item = String>,
+    option: &'static str,
+) -> Result<T, CliError> {
+    let value = value(args, option)?;
+
+    value
+        .parse()
+        .map_err(|_| CliError::InvalidValue { option, value })
+}
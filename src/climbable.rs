@@ -0,0 +1,23 @@
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::Sensor;
+
+#[derive(Component, Reflect, Clone)]
+#[reflect(Component)]
+#[require(Sensor)]
+pub struct Climbable {
+    pub speed: f32,
+}
+
+impl Default for Climbable {
+    fn default() -> Self {
+        Self { speed: 2.0 }
+    }
+}
+
+pub struct ClimbablePlugin;
+
+impl Plugin for ClimbablePlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Climbable>();
+    }
+}
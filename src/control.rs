@@ -1,66 +1,633 @@
+use std::{error::Error, fmt, fs, io, path::Path};
+
 use bevy::{
     app::Plugin,
-    ecs::{component::Component, system::Resource},
-    input::{keyboard::KeyCode, mouse::MouseMotion},
+    ecs::{
+        component::Component,
+        system::{Resource, SystemParam},
+    },
+    input::{keyboard::KeyCode, mouse::MouseMotion, InputSystem},
     math::Vec2,
     prelude::*,
+    utils::HashMap,
 };
+use serde::{Deserialize, Serialize};
 
 #[derive(Component, Reflect)]
 #[reflect(Component)]
+#[require(Input, InputSource)]
 pub struct Control;
 
-#[derive(Resource, Reflect)]
+#[derive(Component, Reflect, Clone, Copy, Default, PartialEq, Eq, Debug)]
+#[reflect(Component)]
+pub enum InputSource {
+    #[default]
+    Any,
+    KeyboardMouse,
+    Gamepad(Entity),
+}
+
+impl InputSource {
+    fn has_keyboard(&self) -> bool {
+        matches!(self, InputSource::Any | InputSource::KeyboardMouse)
+    }
+
+    fn has_gamepad(&self, gamepad: Entity) -> bool {
+        match self {
+            InputSource::Any => true,
+            InputSource::KeyboardMouse => false,
+            InputSource::Gamepad(assigned) => *assigned == gamepad,
+        }
+    }
+}
+
+pub const BINDINGS_PATH: &str = "bindings.json";
+
+#[derive(Reflect, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    MoveForward,
+    MoveLeft,
+    MoveRight,
+    MoveBackward,
+    SwimUp,
+    SwimDown,
+    Jump,
+    Crouch,
+    Run,
+    Pause,
+    SwitchFullScreen,
+    Record,
+    Replay,
+    NextLevel,
+    QuickSave,
+    QuickLoad,
+    SwitchCamera,
+    CycleSpectate,
+    FreeFly,
+    Interact,
+    Grab,
+    Throw,
+    RotateHeld,
+    Fire,
+}
+
+#[derive(PartialEq)]
+enum ActionContext {
+    Any,
+    Ground,
+    Water,
+}
+
+impl Action {
+    pub const ALL: [Action; 24] = [
+        Action::MoveForward,
+        Action::MoveLeft,
+        Action::MoveRight,
+        Action::MoveBackward,
+        Action::SwimUp,
+        Action::SwimDown,
+        Action::Jump,
+        Action::Crouch,
+        Action::Run,
+        Action::Pause,
+        Action::SwitchFullScreen,
+        Action::Record,
+        Action::Replay,
+        Action::NextLevel,
+        Action::QuickSave,
+        Action::QuickLoad,
+        Action::SwitchCamera,
+        Action::CycleSpectate,
+        Action::FreeFly,
+        Action::Interact,
+        Action::Grab,
+        Action::Throw,
+        Action::RotateHeld,
+        Action::Fire,
+    ];
+
+    fn context(&self) -> ActionContext {
+        match self {
+            Action::Jump | Action::Crouch => ActionContext::Ground,
+            Action::SwimUp | Action::SwimDown => ActionContext::Water,
+            _ => ActionContext::Any,
+        }
+    }
+
+    fn overlaps(&self, other: &Action) -> bool {
+        let (a, b) = (self.context(), other.context());
+
+        a == ActionContext::Any || b == ActionContext::Any || a == b
+    }
+}
+
+#[derive(Reflect, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Button {
+    Key(KeyCode),
+    Mouse(MouseButton),
+    Gamepad(GamepadButton),
+}
+
+#[derive(
+    Reflect, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum Modifier {
+    Shift,
+    Control,
+    Alt,
+    Super,
+}
+
+impl Modifier {
+    fn keys(&self) -> [KeyCode; 2] {
+        match self {
+            Modifier::Shift => [KeyCode::ShiftLeft, KeyCode::ShiftRight],
+            Modifier::Control => [KeyCode::ControlLeft, KeyCode::ControlRight],
+            Modifier::Alt => [KeyCode::AltLeft, KeyCode::AltRight],
+            Modifier::Super => [KeyCode::SuperLeft, KeyCode::SuperRight],
+        }
+    }
+}
+
+#[derive(Reflect, Serialize, Deserialize, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Binding {
+    pub button: Button,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub modifiers: Vec<Modifier>,
+}
+
+impl Binding {
+    pub fn key(key: KeyCode) -> Self {
+        Self {
+            button: Button::Key(key),
+            modifiers: Vec::new(),
+        }
+    }
+
+    pub fn mouse(button: MouseButton) -> Self {
+        Self {
+            button: Button::Mouse(button),
+            modifiers: Vec::new(),
+        }
+    }
+
+    pub fn gamepad(button: GamepadButton) -> Self {
+        Self {
+            button: Button::Gamepad(button),
+            modifiers: Vec::new(),
+        }
+    }
+
+    pub fn with_modifier(mut self, modifier: Modifier) -> Self {
+        self.modifiers.push(modifier);
+        self.modifiers.sort();
+        self.modifiers.dedup();
+        self
+    }
+
+    fn modifiers_pressed(&self, devices: &Devices, source: InputSource) -> bool {
+        self.modifiers
+            .iter()
+            .all(|modifier| source.has_keyboard() && devices.keyboard.any_pressed(modifier.keys()))
+    }
+
+    pub fn pressed(&self, devices: &Devices, source: InputSource) -> bool {
+        devices.pressed(self.button, source) && self.modifiers_pressed(devices, source)
+    }
+
+    pub fn just_pressed(&self, devices: &Devices, source: InputSource) -> bool {
+        devices.just_pressed(self.button, source) && self.modifiers_pressed(devices, source)
+    }
+}
+
+impl fmt::Display for Binding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for modifier in self.modifiers.iter() {
+            write!(f, "{:?}+", modifier)?;
+        }
+
+        match self.button {
+            Button::Key(key) => write!(f, "{:?}", key),
+            Button::Mouse(button) => write!(f, "Mouse{:?}", button),
+            Button::Gamepad(button) => write!(f, "Gamepad{:?}", button),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum BindingsError {
+    Io(io::Error),
+    Parse(serde_json::Error),
+    Missing(Action),
+    Conflict {
+        binding: Binding,
+        first: Action,
+        second: Action,
+    },
+    InvalidSensitivity {
+        setting: &'static str,
+        value: f32,
+    },
+    InvalidDeadzone(f32),
+}
+
+impl fmt::Display for BindingsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BindingsError::Io(error) => write!(f, "failed to access bindings file: {}", error),
+            BindingsError::Parse(error) => write!(f, "failed to parse bindings: {}", error),
+            BindingsError::Missing(action) => write!(f, "no bindings for action {:?}", action),
+            BindingsError::Conflict {
+                binding,
+                first,
+                second,
+            } => write!(
+                f,
+                "{} is bound to both {:?} and {:?}",
+                binding, first, second
+            ),
+            BindingsError::InvalidSensitivity { setting, value } => {
+                write!(f, "{} must be a positive number, got {}", setting, value)
+            }
+            BindingsError::InvalidDeadzone(value) => {
+                write!(f, "gamepad deadzone must be in [0, 1), got {}", value)
+            }
+        }
+    }
+}
+
+impl Error for BindingsError {}
+
+impl From<io::Error> for BindingsError {
+    fn from(error: io::Error) -> Self {
+        BindingsError::Io(error)
+    }
+}
+
+impl From<serde_json::Error> for BindingsError {
+    fn from(error: serde_json::Error) -> Self {
+        BindingsError::Parse(error)
+    }
+}
+
+#[derive(Resource, Reflect, Serialize, Deserialize, Clone)]
 #[reflect(Resource)]
+#[serde(default)]
 pub struct Bindings {
-    pub move_forward: KeyCode,
-    pub move_left: KeyCode,
-    pub move_right: KeyCode,
-    pub move_backward: KeyCode,
-    pub swim_up: KeyCode,
-    pub swim_down: KeyCode,
-    pub jump: KeyCode,
-    pub crouch: KeyCode,
-    pub run: KeyCode,
-    pub pause: KeyCode,
-    pub switch_full_screen: KeyCode,
+    pub actions: HashMap<Action, Vec<Binding>>,
     pub mouse_sensitivity: f32,
+    pub gamepad_sensitivity: f32,
+    pub gamepad_deadzone: f32,
 }
 
 impl Default for Bindings {
     fn default() -> Self {
         Self {
-            move_forward: KeyCode::KeyW,
-            move_left: KeyCode::KeyA,
-            move_right: KeyCode::KeyD,
-            move_backward: KeyCode::KeyS,
-            jump: KeyCode::Space,
-            crouch: KeyCode::ControlLeft,
-            run: KeyCode::ShiftLeft,
-            pause: KeyCode::Escape,
-            switch_full_screen: KeyCode::F11,
+            actions: HashMap::from_iter([
+                (
+                    Action::MoveForward,
+                    vec![Binding::key(KeyCode::KeyW), Binding::key(KeyCode::ArrowUp)],
+                ),
+                (
+                    Action::MoveLeft,
+                    vec![
+                        Binding::key(KeyCode::KeyA),
+                        Binding::key(KeyCode::ArrowLeft),
+                    ],
+                ),
+                (
+                    Action::MoveRight,
+                    vec![
+                        Binding::key(KeyCode::KeyD),
+                        Binding::key(KeyCode::ArrowRight),
+                    ],
+                ),
+                (
+                    Action::MoveBackward,
+                    vec![
+                        Binding::key(KeyCode::KeyS),
+                        Binding::key(KeyCode::ArrowDown),
+                    ],
+                ),
+                (
+                    Action::SwimUp,
+                    vec![
+                        Binding::key(KeyCode::Space),
+                        Binding::gamepad(GamepadButton::South),
+                    ],
+                ),
+                (
+                    Action::SwimDown,
+                    vec![
+                        Binding::key(KeyCode::ControlLeft),
+                        Binding::gamepad(GamepadButton::LeftTrigger2),
+                    ],
+                ),
+                (
+                    Action::Jump,
+                    vec![
+                        Binding::key(KeyCode::Space),
+                        Binding::gamepad(GamepadButton::South),
+                    ],
+                ),
+                (
+                    Action::Crouch,
+                    vec![
+                        Binding::key(KeyCode::ControlLeft),
+                        Binding::gamepad(GamepadButton::LeftTrigger2),
+                    ],
+                ),
+                (
+                    Action::Run,
+                    vec![
+                        Binding::key(KeyCode::ShiftLeft),
+                        Binding::gamepad(GamepadButton::RightTrigger2),
+                    ],
+                ),
+                (
+                    Action::Pause,
+                    vec![
+                        Binding::key(KeyCode::Escape),
+                        Binding::gamepad(GamepadButton::Start),
+                    ],
+                ),
+                (
+                    Action::SwitchFullScreen,
+                    vec![
+                        Binding::key(KeyCode::F11),
+                        Binding::key(KeyCode::Enter).with_modifier(Modifier::Alt),
+                    ],
+                ),
+                (Action::Record, vec![Binding::key(KeyCode::F9)]),
+                (Action::Replay, vec![Binding::key(KeyCode::F10)]),
+                (Action::NextLevel, vec![Binding::key(KeyCode::F5)]),
+                (Action::QuickSave, vec![Binding::key(KeyCode::F6)]),
+                (Action::QuickLoad, vec![Binding::key(KeyCode::F8)]),
+                (
+                    Action::SwitchCamera,
+                    vec![
+                        Binding::key(KeyCode::KeyV),
+                        Binding::gamepad(GamepadButton::Select),
+                    ],
+                ),
+                (Action::CycleSpectate, vec![Binding::key(KeyCode::Tab)]),
+                (Action::FreeFly, vec![Binding::key(KeyCode::F3)]),
+                (
+                    Action::Interact,
+                    vec![
+                        Binding::key(KeyCode::KeyF),
+                        Binding::gamepad(GamepadButton::West),
+                    ],
+                ),
+                (
+                    Action::Grab,
+                    vec![
+                        Binding::mouse(MouseButton::Right),
+                        Binding::gamepad(GamepadButton::North),
+                    ],
+                ),
+                (
+                    Action::Throw,
+                    vec![
+                        Binding::mouse(MouseButton::Left),
+                        Binding::gamepad(GamepadButton::East),
+                    ],
+                ),
+                (
+                    Action::RotateHeld,
+                    vec![
+                        Binding::key(KeyCode::KeyR),
+                        Binding::gamepad(GamepadButton::RightThumb),
+                    ],
+                ),
+                (
+                    Action::Fire,
+                    vec![
+                        Binding::key(KeyCode::KeyE),
+                        Binding::gamepad(GamepadButton::RightTrigger),
+                    ],
+                ),
+            ]),
             mouse_sensitivity: 0.002,
-            swim_up: KeyCode::Space,
-            swim_down: KeyCode::ControlLeft,
+            gamepad_sensitivity: 3.0,
+            gamepad_deadzone: 0.15,
         }
     }
 }
 
-#[derive(Resource, Default, Reflect, PartialEq)]
-#[reflect(Resource)]
+impl Bindings {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, BindingsError> {
+        let mut bindings: Self = serde_json::from_str(&fs::read_to_string(path)?)?;
+
+        for binding in bindings.actions.values_mut().flatten() {
+            binding.modifiers.sort();
+            binding.modifiers.dedup();
+        }
+
+        for (action, defaults) in Bindings::default().actions {
+            bindings.actions.entry(action).or_insert(defaults);
+        }
+
+        bindings.validate()?;
+
+        Ok(bindings)
+    }
+
+    pub fn load_or_default(path: impl AsRef<Path>) -> Self {
+        let path = path.as_ref();
+
+        match Self::load(path) {
+            Ok(bindings) => bindings,
+            Err(BindingsError::Io(error)) if error.kind() == io::ErrorKind::NotFound => {
+                let bindings = Self::default();
+
+                if let Err(error) = bindings.save(path) {
+                    warn!("{}", error);
+                }
+
+                bindings
+            }
+            Err(error) => {
+                error!("{} ({}), using default bindings", error, path.display());
+
+                Self::default()
+            }
+        }
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), BindingsError> {
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+
+        Ok(())
+    }
+
+    pub fn validate(&self) -> Result<(), BindingsError> {
+        for (setting, value) in [
+            ("mouse_sensitivity", self.mouse_sensitivity),
+            ("gamepad_sensitivity", self.gamepad_sensitivity),
+        ] {
+            if !value.is_finite() || value <= 0.0 {
+                return Err(BindingsError::InvalidSensitivity { setting, value });
+            }
+        }
+
+        if !(0.0..1.0).contains(&self.gamepad_deadzone) {
+            return Err(BindingsError::InvalidDeadzone(self.gamepad_deadzone));
+        }
+
+        for (index, first) in Action::ALL.iter().enumerate() {
+            let Some(first_bindings) = self.actions.get(first) else {
+                return Err(BindingsError::Missing(*first));
+            };
+
+            for second in Action::ALL[index + 1..].iter() {
+                if !first.overlaps(second) {
+                    continue;
+                }
+
+                let Some(second_bindings) = self.actions.get(second) else {
+                    continue;
+                };
+
+                if let Some(binding) = first_bindings
+                    .iter()
+                    .find(|binding| second_bindings.contains(binding))
+                {
+                    return Err(BindingsError::Conflict {
+                        binding: binding.clone(),
+                        first: *first,
+                        second: *second,
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn shadowed(&self, binding: &Binding, devices: &Devices, source: InputSource) -> bool {
+        self.actions.values().flatten().any(|other| {
+            other.button == binding.button
+                && other.modifiers.len() > binding.modifiers.len()
+                && binding
+                    .modifiers
+                    .iter()
+                    .all(|modifier| other.modifiers.contains(modifier))
+                && other.modifiers_pressed(devices, source)
+        })
+    }
+
+    pub fn pressed(&self, action: Action, devices: &Devices) -> bool {
+        self.pressed_on(action, devices, InputSource::Any)
+    }
+
+    pub fn just_pressed(&self, action: Action, devices: &Devices) -> bool {
+        self.just_pressed_on(action, devices, InputSource::Any)
+    }
+
+    pub fn pressed_on(&self, action: Action, devices: &Devices, source: InputSource) -> bool {
+        self.actions.get(&action).is_some_and(|bindings| {
+            bindings.iter().any(|binding| {
+                binding.pressed(devices, source) && !self.shadowed(binding, devices, source)
+            })
+        })
+    }
+
+    pub fn just_pressed_on(&self, action: Action, devices: &Devices, source: InputSource) -> bool {
+        self.actions.get(&action).is_some_and(|bindings| {
+            bindings.iter().any(|binding| {
+                binding.just_pressed(devices, source) && !self.shadowed(binding, devices, source)
+            })
+        })
+    }
+}
+
+#[derive(SystemParam)]
+pub struct Devices<'w, 's> {
+    keyboard: Res<'w, ButtonInput<KeyCode>>,
+    mouse: Res<'w, ButtonInput<MouseButton>>,
+    gamepads: Query<'w, 's, (Entity, &'static Gamepad)>,
+}
+
+impl<'w, 's> Devices<'w, 's> {
+    fn gamepads(&self, source: InputSource) -> impl Iterator<Item = &Gamepad> {
+        self.gamepads
+            .iter()
+            .filter(move |(entity, _)| source.has_gamepad(*entity))
+            .map(|(_, gamepad)| gamepad)
+    }
+
+    fn pressed(&self, button: Button, source: InputSource) -> bool {
+        match button {
+            Button::Key(key) => source.has_keyboard() && self.keyboard.pressed(key),
+            Button::Mouse(button) => source.has_keyboard() && self.mouse.pressed(button),
+            Button::Gamepad(button) => self.gamepads(source).any(|gamepad| gamepad.pressed(button)),
+        }
+    }
+
+    fn just_pressed(&self, button: Button, source: InputSource) -> bool {
+        match button {
+            Button::Key(key) => source.has_keyboard() && self.keyboard.just_pressed(key),
+            Button::Mouse(button) => source.has_keyboard() && self.mouse.just_pressed(button),
+            Button::Gamepad(button) => self
+                .gamepads(source)
+                .any(|gamepad| gamepad.just_pressed(button)),
+        }
+    }
+
+    fn left_stick(&self, deadzone: f32, source: InputSource) -> Vec2 {
+        self.gamepads(source)
+            .map(|gamepad| apply_deadzone(gamepad.left_stick(), deadzone))
+            .sum()
+    }
+
+    fn right_stick(&self, deadzone: f32, source: InputSource) -> Vec2 {
+        self.gamepads(source)
+            .map(|gamepad| apply_deadzone(gamepad.right_stick(), deadzone))
+            .sum()
+    }
+}
+
+fn apply_deadzone(stick: Vec2, deadzone: f32) -> Vec2 {
+    let length = stick.length();
+
+    if length <= deadzone {
+        return Vec2::ZERO;
+    }
+
+    stick * ((length.min(1.0) - deadzone) / (1.0 - deadzone) / length)
+}
+
+#[derive(Component, Default, Reflect, PartialEq, Clone, Serialize, Deserialize)]
+#[reflect(Component)]
+#[serde(default)]
 pub struct Input {
     pub moving: Vec2,
     looking: Vec2,
     jumping: bool,
+    pub holding_jump: bool,
     pub running: bool,
     pub swimming_up: bool,
     pub swimming_down: bool,
     pub crouching: bool,
     pub pausing: bool,
-    full_screen_switching: bool,
+    switching_camera: bool,
+    interacting: bool,
+    grabbing: bool,
+    throwing: bool,
+    firing: bool,
+    pub rotating_held: bool,
 }
 
 impl Input {
+    pub fn jump(&mut self) {
+        self.jumping = true;
+        self.holding_jump = true;
+    }
+
     pub fn jumping(&mut self) -> bool {
         if self.jumping {
             self.jumping = false;
@@ -70,15 +637,79 @@ impl Input {
         false
     }
 
-    pub fn full_screen_switching(&mut self) -> bool {
-        if self.full_screen_switching {
-            self.full_screen_switching = false;
+    pub fn switch_camera(&mut self) {
+        self.switching_camera = true;
+    }
+
+    pub fn switching_camera(&mut self) -> bool {
+        if self.switching_camera {
+            self.switching_camera = false;
+            return true;
+        }
+
+        false
+    }
+
+    pub fn interact(&mut self) {
+        self.interacting = true;
+    }
+
+    pub fn interacting(&mut self) -> bool {
+        if self.interacting {
+            self.interacting = false;
+            return true;
+        }
+
+        false
+    }
+
+    pub fn grab(&mut self) {
+        self.grabbing = true;
+    }
+
+    pub fn grabbing(&mut self) -> bool {
+        if self.grabbing {
+            self.grabbing = false;
+            return true;
+        }
+
+        false
+    }
+
+    pub fn throw(&mut self) {
+        self.throwing = true;
+    }
+
+    pub fn throwing(&mut self) -> bool {
+        if self.throwing {
+            self.throwing = false;
+            return true;
+        }
+
+        false
+    }
+
+    pub fn fire(&mut self) {
+        self.firing = true;
+    }
+
+    pub fn firing(&mut self) -> bool {
+        if self.firing {
+            self.firing = false;
             return true;
         }
 
         false
     }
 
+    pub fn look(&mut self, delta: Vec2) {
+        self.looking += delta;
+    }
+
+    pub fn peek_looking(&self) -> Vec2 {
+        self.looking
+    }
+
     pub fn looking(&mut self) -> Vec2 {
         let result = self.looking;
 
@@ -95,53 +726,86 @@ pub struct ControlPlugin;
 
 impl Plugin for ControlPlugin {
     fn build(&self, app: &mut App) {
-        app.register_type::<Control>();
+        app.register_type::<Control>()
+            .register_type::<InputSource>();
 
-        app.register_type::<Bindings>()
-            .insert_resource(Bindings::default());
+        app.register_type::<Bindings>().init_resource::<Bindings>();
 
-        app.register_type::<Input>()
-            .insert_resource(Input::default());
+        app.register_type::<Input>();
 
-        app.add_systems(PreUpdate, update.in_set(ControlSystems));
+        app.add_systems(PreUpdate, update.in_set(ControlSystems).after(InputSystem));
     }
 }
 
 fn update(
+    time: Res<Time>,
     mut mouse: EventReader<MouseMotion>,
-    keyboard: Res<ButtonInput<KeyCode>>,
+    devices: Devices,
     controls: Res<Bindings>,
-    mut input: ResMut<Input>,
+    mut entity_q: Query<(&mut Input, &InputSource), With<Control>>,
 ) {
+    let mut mouse_looking = Vec2::ZERO;
+
     for event in mouse.read().into_iter() {
-        input.looking += Vec2::new(-event.delta.x, -event.delta.y) * controls.mouse_sensitivity;
+        mouse_looking += Vec2::new(-event.delta.x, -event.delta.y) * controls.mouse_sensitivity;
     }
 
-    input.moving = Vec2::ZERO;
+    for (mut input, source) in entity_q.iter_mut() {
+        let source = *source;
 
-    if keyboard.pressed(controls.move_left) {
-        input.moving += Vec2::new(-1.0, 0.0);
-    }
+        let pressed = |action| controls.pressed_on(action, &devices, source);
 
-    if keyboard.pressed(controls.move_right) {
-        input.moving += Vec2::new(1.0, 0.0);
-    }
+        let just_pressed = |action| controls.just_pressed_on(action, &devices, source);
 
-    if keyboard.pressed(controls.move_forward) {
-        input.moving += Vec2::new(0.0, -1.0);
-    }
+        let mut looking = if source.has_keyboard() {
+            mouse_looking
+        } else {
+            Vec2::ZERO
+        };
 
-    if keyboard.pressed(controls.move_backward) {
-        input.moving += Vec2::new(0.0, 1.0);
-    }
+        let right_stick = devices.right_stick(controls.gamepad_deadzone, source);
+
+        looking += Vec2::new(-right_stick.x, right_stick.y)
+            * controls.gamepad_sensitivity
+            * time.delta_secs();
 
-    input.jumping = input.jumping || keyboard.just_pressed(controls.jump);
-    input.full_screen_switching =
-        input.full_screen_switching || keyboard.just_pressed(controls.switch_full_screen);
-    input.running = keyboard.pressed(controls.run);
-    input.crouching = keyboard.pressed(controls.crouch);
-    input.swimming_up = keyboard.pressed(controls.swim_up);
-    input.swimming_down = keyboard.pressed(controls.swim_down);
+        let mut moving = Vec2::ZERO;
 
-    input.pausing = keyboard.just_pressed(controls.pause);
+        if pressed(Action::MoveLeft) {
+            moving += Vec2::new(-1.0, 0.0);
+        }
+
+        if pressed(Action::MoveRight) {
+            moving += Vec2::new(1.0, 0.0);
+        }
+
+        if pressed(Action::MoveForward) {
+            moving += Vec2::new(0.0, -1.0);
+        }
+
+        if pressed(Action::MoveBackward) {
+            moving += Vec2::new(0.0, 1.0);
+        }
+
+        let left_stick = devices.left_stick(controls.gamepad_deadzone, source);
+
+        moving += Vec2::new(left_stick.x, -left_stick.y);
+
+        input.looking += looking;
+        input.moving = moving.clamp_length_max(1.0);
+        input.jumping = input.jumping || just_pressed(Action::Jump);
+        input.switching_camera = input.switching_camera || just_pressed(Action::SwitchCamera);
+        input.interacting = input.interacting || just_pressed(Action::Interact);
+        input.grabbing = input.grabbing || just_pressed(Action::Grab);
+        input.throwing = input.throwing || just_pressed(Action::Throw);
+        input.firing = input.firing || just_pressed(Action::Fire);
+        input.rotating_held = pressed(Action::RotateHeld);
+        input.holding_jump = pressed(Action::Jump);
+        input.running = pressed(Action::Run);
+        input.crouching = pressed(Action::Crouch);
+        input.swimming_up = pressed(Action::SwimUp);
+        input.swimming_down = pressed(Action::SwimDown);
+
+        input.pausing = just_pressed(Action::Pause);
+    }
 }
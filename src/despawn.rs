@@ -1,4 +1,4 @@
-use std::time::{Duration, Instant};
+use std::time::Duration;
 
 use bevy::{
     app::Plugin,
@@ -20,7 +20,7 @@ use crate::library::Spawnable;
 pub struct Despawn {
     recursive: bool,
     timeout: Option<Duration>,
-    crated_at: Option<Instant>,
+    elapsed: Duration,
 }
 
 impl Despawn {
@@ -28,7 +28,7 @@ impl Despawn {
         Self {
             recursive: false,
             timeout: None,
-            crated_at: None,
+            elapsed: Duration::ZERO,
         }
     }
 
@@ -36,7 +36,7 @@ impl Despawn {
         Self {
             recursive: false,
             timeout: Some(duration),
-            crated_at: None,
+            elapsed: Duration::ZERO,
         }
     }
 
@@ -46,15 +46,11 @@ impl Despawn {
     }
 
     pub fn is_time_up(&self) -> bool {
-        let Some(crated_at) = self.crated_at else {
-            return true;
-        };
-
         let Some(timeout) = self.timeout else {
             return true;
         };
 
-        crated_at.elapsed() > timeout
+        self.elapsed > timeout
     }
 }
 
@@ -66,10 +62,6 @@ impl Component for Despawn {
             let despawn = world.get::<Despawn>(entity).cloned().unwrap();
 
             if despawn.timeout.is_some() {
-                let mut despawn = world.get_mut::<Despawn>(entity).unwrap();
-
-                despawn.crated_at = Some(Instant::now());
-
                 return;
             }
 
@@ -97,8 +89,10 @@ impl Plugin for DespawnPlugin {
     }
 }
 
-fn update(mut commands: Commands, entity_q: Query<(Entity, &Despawn)>) {
-    for (entity, despawn) in entity_q.iter() {
+fn update(mut commands: Commands, time: Res<Time>, mut entity_q: Query<(Entity, &mut Despawn)>) {
+    for (entity, mut despawn) in entity_q.iter_mut() {
+        despawn.elapsed += time.delta();
+
         if !despawn.is_time_up() {
             continue;
         }
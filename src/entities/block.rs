@@ -1,31 +1,44 @@
-use bevy::prelude::*;
+use bevy::{
+    ecs::{component::ComponentId, world::DeferredWorld},
+    prelude::*,
+};
 use bevy_rapier3d::prelude::*;
 
-use crate::{library::Spawnable, with_material::WithMaterial, with_mesh::WithMesh};
+use crate::{library::Spawnable, save::Saved, with_material::WithMaterial, with_mesh::WithMesh};
+
+#[derive(Component, Reflect, Clone)]
+#[component(on_add = build)]
+#[reflect(Component)]
+#[require(Transform, ReadMassProperties, Velocity, Saved)]
+pub struct Block {
+    pub size: Vec3,
+}
+
+fn build(mut world: DeferredWorld<'_>, entity: Entity, _: ComponentId) {
+    let size = world.get::<Block>(entity).unwrap().size;
+
+    world.commands().entity(entity).insert((
+        WithMesh::new(Cuboid::from_size(size)),
+        WithMaterial::new(Color::srgb_u8(255, 255, 255)),
+        Collider::cuboid(size.x / 2.0, size.y / 2.0, size.z / 2.0),
+    ));
+}
 
 #[derive(Bundle, Clone)]
 pub struct BlockBundle {
     name: Name,
-    mesh: WithMesh,
-    material: WithMaterial,
-    collider: Collider,
+    block: Block,
     body: RigidBody,
     collider_mass_properties: ColliderMassProperties,
-    velocity: Velocity,
-    transform: Transform,
 }
 
 impl Default for BlockBundle {
     fn default() -> Self {
         Self {
             name: Name::new("block"),
-            mesh: WithMesh::new(Cuboid::new(1.0, 1.0, 1.0)),
-            material: WithMaterial::new(Color::srgb_u8(255, 255, 255)),
-            collider: Collider::cuboid(0.5, 0.5, 0.5),
+            block: Block { size: Vec3::ONE },
             body: RigidBody::Dynamic,
             collider_mass_properties: ColliderMassProperties::Mass(200.0),
-            velocity: Velocity::default(),
-            transform: Transform::default(),
         }
     }
 }
@@ -33,9 +46,9 @@ impl Default for BlockBundle {
 impl BlockBundle {
     pub fn new(hx: f32, hy: f32, hz: f32) -> Self {
         Self {
-            name: Name::new("block"),
-            mesh: WithMesh::new(Cuboid::new(hx, hy, hz)),
-            collider: Collider::cuboid(hx / 2.0, hy / 2.0, hz / 2.0),
+            block: Block {
+                size: Vec3::new(hx, hy, hz),
+            },
             ..default()
         }
     }
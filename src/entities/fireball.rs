@@ -1,13 +1,18 @@
 use std::time::Duration;
 
-use bevy::{color::palettes::css::ORANGE, ecs::entity, pbr::NotShadowCaster, prelude::*};
+use bevy::{
+    color::palettes::css::ORANGE,
+    ecs::{component::ComponentId, entity, world::DeferredWorld},
+    pbr::NotShadowCaster,
+    prelude::*,
+};
 use bevy_rapier3d::prelude::{
     ActiveEvents, Collider, CollisionEvent, GravityScale, LockedAxes, RigidBody, Sensor,
 };
 
 use crate::{
-    billboard::BillboardMaterial, despawn::Despawn, explosion::Explosion, library::Spawnable,
-    AppSystems,
+    billboard::BillboardMaterial, despawn::Despawn, explosion::Explosion, levels::LevelScoped,
+    library::Spawnable, save::Saved, AppSystems,
 };
 
 #[derive(Resource, PartialEq, Clone)]
@@ -17,50 +22,61 @@ struct FireballAssets {
 }
 
 #[derive(Component, Reflect, Clone)]
+#[component(on_add = ignite)]
 #[reflect(Component)]
-#[require(Transform)]
+#[require(Transform, LevelScoped, Saved)]
 pub struct Fireball;
 
 impl Spawnable for Fireball {
     fn spawn<'a>(&self, commands: &'a mut Commands) -> EntityCommands<'a> {
-        let entity = commands
-            .spawn(self.clone())
-            .queue(|entity: Entity, world: &mut World| {
-                let assets = world.get_resource::<FireballAssets>().cloned().unwrap();
-
-                let mut commands = world.commands();
-
-                commands.entity(entity).insert((
-                    Name::new("fireball"),
-                    LockedAxes::ROTATION_LOCKED,
-                    Mesh3d(assets.mesh.clone()),
-                    MeshMaterial3d(assets.material.clone()),
-                    NotShadowCaster,
-                    RigidBody::Dynamic,
-                    GravityScale(0.0),
-                    Collider::ball(0.3),
-                    ActiveEvents::COLLISION_EVENTS,
-                    PointLight {
-                        intensity: 100_000.0,
-                        color: ORANGE.into(),
-                        shadows_enabled: true,
-                        ..default()
-                    },
-                ));
-            })
-            .id();
-
-        commands.entity(entity)
+        commands.spawn(self.clone())
     }
 }
 
+fn ignite(mut world: DeferredWorld<'_>, entity: Entity, _: ComponentId) {
+    let assets = world.get_resource::<FireballAssets>().cloned();
+
+    let mut commands = world.commands();
+
+    let mut commands = commands.entity(entity);
+
+    commands.insert((
+        Name::new("fireball"),
+        LockedAxes::ROTATION_LOCKED,
+        RigidBody::Dynamic,
+        GravityScale(0.0),
+        Collider::ball(0.3),
+        ActiveEvents::COLLISION_EVENTS,
+    ));
+
+    let Some(assets) = assets else {
+        return;
+    };
+
+    commands.insert((
+        Mesh3d(assets.mesh),
+        MeshMaterial3d(assets.material),
+        NotShadowCaster,
+        PointLight {
+            intensity: 100_000.0,
+            color: ORANGE.into(),
+            shadows_enabled: true,
+            ..default()
+        },
+    ));
+}
+
 pub struct FireballPlugin;
 
 impl Plugin for FireballPlugin {
     fn build(&self, app: &mut App) {
-        app.register_type::<Despawn>()
+        app.register_type::<Fireball>()
+            .register_type::<Despawn>()
             .add_systems(FixedPreUpdate, update)
-            .add_systems(PreStartup, load);
+            .add_systems(
+                PreStartup,
+                load.run_if(resource_exists::<Assets<BillboardMaterial>>),
+            );
     }
 }
 
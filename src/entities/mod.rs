@@ -1,4 +1,32 @@
+use bevy::prelude::*;
+use block::{Block, BlockBundle};
+use fireball::Fireball;
+use player::LocalPlayer;
+use spectator::Spectator;
+use traffic_cone::TrafficCone;
+
+use crate::library::Spawnables;
 pub mod block;
 pub mod fireball;
 pub mod player;
+pub mod spectator;
 pub mod traffic_cone;
+
+pub struct EntitiesPlugin;
+
+impl Plugin for EntitiesPlugin {
+    fn build(&self, app: &mut App) {
+        let mut spawnables = app
+            .register_type::<Block>()
+            .register_type::<TrafficCone>()
+            .init_resource::<Spawnables>()
+            .world_mut()
+            .resource_mut::<Spawnables>();
+
+        spawnables.register("player", LocalPlayer);
+        spawnables.register("fireball", Fireball);
+        spawnables.register("block", BlockBundle::default());
+        spawnables.register("traffic_cone", TrafficCone);
+        spawnables.register("spectator", Spectator::default());
+    }
+}
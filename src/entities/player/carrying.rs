@@ -0,0 +1,247 @@
+use std::f32::consts;
+
+use bevy::{
+    ecs::{
+        component::ComponentId,
+        entity::{EntityMapper, MapEntities},
+        reflect::ReflectMapEntities,
+        world::DeferredWorld,
+    },
+    prelude::*,
+};
+use bevy_rapier3d::{
+    plugin::RapierContext,
+    prelude::{ExternalImpulse, ReadMassProperties, RigidBody, SolverGroups, Velocity},
+};
+
+use crate::{control::Input, linker::Linker, ray_caster::RayCaster, shape_caster::ShapeCaster};
+
+use super::{Parameters, HAND_DISTANCE, PLAYER_GROUP};
+
+#[derive(Component, Reflect, Default)]
+#[reflect(Component, MapEntities)]
+pub struct Carrier {
+    pub held: Option<Entity>,
+}
+
+impl MapEntities for Carrier {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.held = self.held.map(|entity| entity_mapper.map_entity(entity));
+    }
+}
+
+#[derive(Component, Reflect)]
+#[component(on_add = ignore_players, on_remove = collide_with_players)]
+#[reflect(Component, MapEntities)]
+pub struct Held {
+    pub by: Entity,
+    pub rotation: Quat,
+    #[reflect(ignore)]
+    previous: Vec<(Entity, Option<SolverGroups>)>,
+}
+
+impl Held {
+    pub fn new(by: Entity, rotation: Quat) -> Self {
+        Self {
+            by,
+            rotation,
+            previous: Vec::new(),
+        }
+    }
+}
+
+impl MapEntities for Held {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.by = entity_mapper.map_entity(self.by);
+    }
+}
+
+fn descendants(world: &DeferredWorld, entity: Entity) -> Vec<Entity> {
+    let mut entities = vec![entity];
+
+    let mut index = 0;
+
+    while index < entities.len() {
+        if let Some(children) = world.get::<Children>(entities[index]) {
+            entities.extend(children.iter());
+        }
+
+        index += 1;
+    }
+
+    entities
+}
+
+fn ignore_players(mut world: DeferredWorld<'_>, entity: Entity, _: ComponentId) {
+    let previous = descendants(&world, entity)
+        .into_iter()
+        .map(|entity| (entity, world.get::<SolverGroups>(entity).copied()))
+        .collect::<Vec<_>>();
+
+    for (entity, groups) in previous.iter() {
+        let groups = groups.unwrap_or_default();
+
+        world
+            .commands()
+            .entity(*entity)
+            .try_insert(SolverGroups::new(
+                groups.memberships,
+                groups.filters.difference(PLAYER_GROUP),
+            ));
+    }
+
+    world.get_mut::<Held>(entity).unwrap().previous = previous;
+}
+
+fn collide_with_players(mut world: DeferredWorld<'_>, entity: Entity, _: ComponentId) {
+    let previous = world.get::<Held>(entity).unwrap().previous.clone();
+
+    for (entity, groups) in previous {
+        let mut commands = world.commands();
+
+        let mut entity = commands.entity(entity);
+
+        match groups {
+            Some(groups) => entity.try_insert(groups),
+            None => entity.remove::<SolverGroups>(),
+        };
+    }
+}
+
+pub fn excluding(entity_q: Query<(&Carrier, &Linker)>, mut caster_q: Query<&mut ShapeCaster>) {
+    for (carrier, linker) in entity_q.iter() {
+        for name in ["cast_up", "cast_down"] {
+            let mut caster = caster_q.get_mut(*linker.get(name).unwrap()).unwrap();
+
+            caster.exclude_body = carrier.held;
+        }
+    }
+}
+
+pub fn grabbing(
+    mut commands: Commands,
+    mut entity_q: Query<(Entity, &Linker, &Parameters, &mut Carrier, &mut Input)>,
+    rapier: Single<&RapierContext>,
+    caster_q: Query<&RayCaster>,
+    head_q: Query<&GlobalTransform>,
+    body_q: Query<(&RigidBody, &ReadMassProperties, &GlobalTransform), Without<Held>>,
+) {
+    for (entity, linker, parameters, mut carrier, mut input) in entity_q.iter_mut() {
+        let head = head_q.get(*linker.get("head").unwrap()).unwrap();
+
+        if input.throwing() {
+            let Some(held) = carrier.held.take() else {
+                continue;
+            };
+
+            if let Some(mut held) = commands.get_entity(held) {
+                held.remove::<Held>().try_insert(ExternalImpulse {
+                    impulse: head.forward() * parameters.throw_impulse,
+                    ..default()
+                });
+            }
+
+            continue;
+        }
+
+        if !input.grabbing() {
+            continue;
+        }
+
+        if let Some(held) = carrier.held.take() {
+            if let Some(mut held) = commands.get_entity(held) {
+                held.remove::<Held>();
+            }
+
+            continue;
+        }
+
+        let Some(target) = caster_q
+            .get(*linker.get("ray_cast").unwrap())
+            .ok()
+            .and_then(|caster| caster.result.as_ref())
+            .map(|result| {
+                rapier
+                    .collider_parent(result.entity)
+                    .unwrap_or(result.entity)
+            })
+        else {
+            continue;
+        };
+
+        let Ok((body, mass_properties, transform)) = body_q.get(target) else {
+            continue;
+        };
+
+        if *body != RigidBody::Dynamic || mass_properties.mass > parameters.max_carry_mass {
+            continue;
+        }
+
+        commands.entity(target).insert(Held::new(
+            entity,
+            head.rotation().inverse() * transform.rotation(),
+        ));
+
+        carrier.held = Some(target);
+    }
+}
+
+pub fn rotating(mut entity_q: Query<(&Carrier, &mut Input)>, mut held_q: Query<&mut Held>) {
+    for (carrier, mut input) in entity_q.iter_mut() {
+        if !input.rotating_held {
+            continue;
+        }
+
+        let Some(mut held) = carrier.held.and_then(|held| held_q.get_mut(held).ok()) else {
+            continue;
+        };
+
+        let looking = input.looking();
+
+        held.rotation =
+            Quat::from_rotation_y(looking.x) * Quat::from_rotation_x(looking.y) * held.rotation;
+    }
+}
+
+pub fn carrying(
+    mut commands: Commands,
+    mut entity_q: Query<(&Linker, &Parameters, &Transform, &Velocity, &mut Carrier), Without<Held>>,
+    head_q: Query<&Transform>,
+    mut held_q: Query<(&Held, &Transform, &mut Velocity), Without<Carrier>>,
+) {
+    for (linker, parameters, transform, carrier_velocity, mut carrier) in entity_q.iter_mut() {
+        let Some(entity) = carrier.held else {
+            continue;
+        };
+
+        let Ok((held, held_transform, mut velocity)) = held_q.get_mut(entity) else {
+            carrier.held = None;
+
+            continue;
+        };
+
+        let head = transform.mul_transform(*head_q.get(*linker.get("head").unwrap()).unwrap());
+
+        let offset = head.translation + head.forward() * HAND_DISTANCE - held_transform.translation;
+
+        if offset.length() > parameters.carry_break_distance {
+            commands.entity(entity).remove::<Held>();
+
+            carrier.held = None;
+
+            continue;
+        }
+
+        velocity.linvel = carrier_velocity.linvel
+            + (offset * parameters.carry_stiffness).clamp_length_max(parameters.max_carry_speed);
+
+        let (axis, mut angle) =
+            (head.rotation * held.rotation * held_transform.rotation.inverse()).to_axis_angle();
+
+        if angle > consts::PI {
+            angle -= consts::TAU;
+        }
+
+        velocity.angvel = axis * angle * parameters.carry_stiffness;
+    }
+}
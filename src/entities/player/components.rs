@@ -1,23 +1,83 @@
-use bevy::{prelude::Component, reflect::Reflect};
+use std::f32::consts;
+
+use bevy::{
+    ecs::{
+        entity::{EntityMapper, MapEntities},
+        reflect::ReflectMapEntities,
+    },
+    prelude::Component,
+    reflect::Reflect,
+};
 
 use bevy::prelude::*;
 use bevy_rapier3d::prelude::{GravityScale, Velocity};
+use serde::{Deserialize, Serialize};
+
+use crate::{control::Input, gravity::Gravity};
 
-use super::{Player, COLLIDER_HALF_HEIGHT};
+use super::{Player, HALF_HEIGHT};
 
-#[derive(Component, Reflect, PartialEq)]
+#[derive(Component, Reflect, PartialEq, Clone, Serialize, Deserialize)]
 #[reflect(Component)]
-#[require(GravityScale, Status, Velocity)]
+#[serde(default)]
+#[require(GravityScale, Gravity, Status, Velocity, Input)]
 pub struct Parameters {
     pub walking_speed: f32,
     pub falling_speed: f32,
     pub running_speed: f32,
     pub crouching_speed: f32,
     pub swimming_speed: f32,
+    pub swimming_acceleration: f32,
     pub falling_acceleration: f32,
     pub standing_acceleration: f32,
     pub standing_jump_height: f32,
     pub crouching_jump_height: f32,
+    pub max_step_height: f32,
+    pub step_smoothing: f32,
+    pub coyote_time: f32,
+    pub jump_buffer_time: f32,
+    pub jump_release_factor: f32,
+    pub alignment_speed: f32,
+    pub sliding_acceleration: f32,
+    pub sliding_friction: f32,
+    pub min_mantle_height: f32,
+    pub max_mantle_height: f32,
+    pub mantle_speed: f32,
+    pub max_mantle_time: f32,
+    pub safe_falling_speed: f32,
+    pub fall_damage: f32,
+    pub collider_radius: f32,
+    pub skin_width: f32,
+    pub max_slope_angle: f32,
+    pub crouch_ratio: f32,
+    pub max_carry_mass: f32,
+    pub carry_stiffness: f32,
+    pub max_carry_speed: f32,
+    pub carry_break_distance: f32,
+    pub throw_impulse: f32,
+    pub pushing_force: f32,
+}
+
+impl Parameters {
+    pub fn collider_half_height(&self) -> f32 {
+        HALF_HEIGHT - self.collider_radius
+    }
+
+    pub fn crouching_half_height(&self) -> f32 {
+        self.collider_half_height() * self.crouch_ratio
+    }
+
+    pub fn can_standup(&self, up_distance: f32, down_distance: f32) -> bool {
+        up_distance + down_distance >= self.collider_half_height() * 2.0
+    }
+
+    pub fn max_surface_gap(&self) -> f32 {
+        0.03 + self.skin_width
+    }
+
+    pub fn cast_distance(&self) -> f32 {
+        self.collider_half_height() * 2.0 - self.crouching_half_height() + self.skin_width
+    }
 }
 
 impl Default for Parameters {
@@ -31,25 +91,85 @@ impl Default for Parameters {
             standing_jump_height: 4.0,
             crouching_jump_height: 2.0,
             crouching_speed: 2.0,
-            swimming_speed: 60.0,
+            swimming_speed: 3.0,
+            swimming_acceleration: 6.0,
+            max_step_height: 0.3,
+            step_smoothing: 12.0,
+            coyote_time: 0.1,
+            jump_buffer_time: 0.15,
+            jump_release_factor: 0.5,
+            alignment_speed: 5.0,
+            sliding_acceleration: 12.0,
+            sliding_friction: 0.5,
+            min_mantle_height: 0.5,
+            max_mantle_height: 1.6,
+            mantle_speed: 3.0,
+            max_mantle_time: 1.0,
+            safe_falling_speed: 12.0,
+            fall_damage: 10.0,
+            collider_radius: 0.3,
+            skin_width: 0.05,
+            max_slope_angle: consts::PI / 3.8,
+            crouch_ratio: 0.4,
+            max_carry_mass: 25.0,
+            carry_stiffness: 12.0,
+            max_carry_speed: 10.0,
+            carry_break_distance: 1.0,
+            throw_impulse: 40.0,
+            pushing_force: 1500.0,
         }
     }
 }
 
-#[derive(Component, Reflect)]
-#[reflect(Component)]
+#[derive(Component, Reflect, Clone, Serialize, Deserialize)]
+#[reflect(Component, MapEntities)]
+#[serde(default)]
 pub struct Status {
     pub surface: Option<Vec3>,
+    pub slope: Option<Vec3>,
     pub current_collider_height: f32,
     pub can_standup: bool,
+    pub water: Option<Entity>,
+    pub ladder: Option<Entity>,
+    pub platform: Option<Entity>,
+    pub platform_velocity: Vec3,
+    pub coyote_timer: f32,
+    pub jump_buffer_timer: f32,
+    pub is_jumping: bool,
+    pub mantle: Option<Vec3>,
+    pub mantle_timer: f32,
+    pub falling_speed: f32,
+    pub is_standing_blocked: bool,
+    pub step_offset: f32,
 }
 
 impl Default for Status {
     fn default() -> Self {
         Self {
             surface: None,
-            current_collider_height: COLLIDER_HALF_HEIGHT,
+            slope: None,
+            current_collider_height: Parameters::default().collider_half_height(),
             can_standup: true,
+            water: None,
+            ladder: None,
+            platform: None,
+            platform_velocity: Vec3::ZERO,
+            coyote_timer: 0.0,
+            jump_buffer_timer: 0.0,
+            is_jumping: false,
+            mantle: None,
+            mantle_timer: 0.0,
+            falling_speed: 0.0,
+            is_standing_blocked: false,
+            step_offset: 0.0,
         }
     }
 }
+
+impl MapEntities for Status {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.water = self.water.map(|entity| entity_mapper.map_entity(entity));
+        self.ladder = self.ladder.map(|entity| entity_mapper.map_entity(entity));
+        self.platform = self.platform.map(|entity| entity_mapper.map_entity(entity));
+    }
+}
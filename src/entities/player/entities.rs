@@ -8,31 +8,36 @@ use bevy::{
     utils::default,
 };
 use bevy_rapier3d::prelude::{
-    CoefficientCombineRule, Collider, ColliderMassProperties, Friction, LockedAxes, RigidBody,
+    CoefficientCombineRule, Collider, ColliderMassProperties, Friction, LockedAxes,
+    ReadMassProperties, RigidBody,
 };
 
 use crate::{
-    camera_controller::CameraController, library::Spawnable, linker::Linker, ray_caster::RayCaster,
+    camera_controller::{CameraBoom, CameraController, Spectate},
+    control::Control,
+    health::{Death, Health},
+    interaction::Focus,
+    library::Spawnable,
+    linker::Linker,
+    ray_caster::RayCaster,
+    save::Saved,
     shape_caster::ShapeCaster,
 };
 
-use super::{
-    components::*, COLLIDER_CROUCHING_HALF_HEIGHT, COLLIDER_HALF_HEIGHT, COLLIDER_RADIUS,
-    HAND_DISTANCE, SKIN_WIDTH,
-};
+use super::{components::*, Carrier, UsesDefaultTuning, HAND_DISTANCE};
 
 use bevy::prelude::*;
 
-const CAST_DISTANCE: f32 =
-    COLLIDER_HALF_HEIGHT - COLLIDER_CROUCHING_HALF_HEIGHT + COLLIDER_HALF_HEIGHT + SKIN_WIDTH;
-
 pub struct Player;
 
 impl Player {
-    fn bundle() -> impl Bundle {
+    fn bundle(parameters: Parameters) -> impl Bundle {
         (
             Name::new("player"),
-            Collider::capsule_y(COLLIDER_HALF_HEIGHT, COLLIDER_RADIUS),
+            Collider::capsule_y(
+                parameters.collider_half_height(),
+                parameters.collider_radius,
+            ),
             RigidBody::Dynamic,
             LockedAxes::ROTATION_LOCKED,
             Friction {
@@ -40,41 +45,53 @@ impl Player {
                 combine_rule: CoefficientCombineRule::Multiply,
             },
             ColliderMassProperties::Mass(65.0),
-            Parameters::default(),
+            ReadMassProperties::default(),
+            parameters,
+            Health::new(100.0),
+            Death::Respawn,
+            Focus::default(),
+            Carrier::default(),
+            Saved,
         )
     }
 }
 
 impl Spawnable for Player {
     fn spawn<'a>(&self, commands: &'a mut Commands) -> EntityCommands<'a> {
-        let player = commands.spawn(Self::bundle()).id();
+        let parameters = Parameters::default();
+
+        let player = commands.spawn(Self::bundle(parameters.clone())).id();
 
         let head = commands
-            .spawn(Head::bundle(Vec3::Y * COLLIDER_HALF_HEIGHT))
+            .spawn(Head::bundle(Vec3::Y * parameters.collider_half_height()))
             .set_parent(player)
             .id();
 
         let camera = commands.spawn(PlayerCamera::bundle()).set_parent(head).id();
 
+        let boom = commands.spawn(Boom::bundle(player)).set_parent(head).id();
+
         let ray_cast = commands
             .spawn(RayCast::bundle(player))
             .set_parent(head)
             .id();
 
         let cast_up = commands
-            .spawn(ShapeCast::up(player).bundle())
+            .spawn(ShapeCast::up(player).bundle(&parameters))
             .set_parent(player)
             .id();
 
         let cast_down = commands
-            .spawn(ShapeCast::down(player).bundle())
+            .spawn(ShapeCast::down(player).bundle(&parameters))
             .set_parent(player)
             .id();
 
         commands.entity(player).insert((
             CameraController::new(camera),
+            CameraBoom::new(boom),
             Linker::new()
                 .with_link("head", head)
+                .with_link("boom", boom)
                 .with_link("ray_cast", ray_cast)
                 .with_link("cast_up", cast_up)
                 .with_link("cast_down", cast_down),
@@ -84,18 +101,33 @@ impl Spawnable for Player {
     }
 }
 
+pub struct LocalPlayer;
+
+impl Spawnable for LocalPlayer {
+    fn spawn<'a>(&self, commands: &'a mut Commands) -> EntityCommands<'a> {
+        let mut entity = Player.spawn(commands);
+
+        entity.insert((Spectate, Control, UsesDefaultTuning));
+
+        entity
+    }
+}
+
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+#[require(Camera3d, Projection(PlayerCamera::projection))]
 pub struct PlayerCamera;
 
 impl PlayerCamera {
     fn bundle() -> impl Bundle {
-        (
-            Name::new("camera"),
-            Camera3d::default(),
-            Projection::Perspective(PerspectiveProjection {
-                fov: consts::PI / 2.0,
-                ..default()
-            }),
-        )
+        (Name::new("camera"), PlayerCamera)
+    }
+
+    fn projection() -> Projection {
+        Projection::Perspective(PerspectiveProjection {
+            fov: consts::PI / 2.0,
+            ..default()
+        })
     }
 }
 
@@ -105,7 +137,20 @@ impl RayCast {
     pub fn bundle(exclude: Entity) -> impl Bundle {
         (
             Name::new("ray_cast"),
-            RayCaster::new(Vec3::NEG_Z * HAND_DISTANCE).exclude(exclude),
+            RayCaster::new(Vec3::NEG_Z * HAND_DISTANCE)
+                .fixed_update()
+                .exclude(exclude),
+        )
+    }
+}
+
+pub struct Boom;
+
+impl Boom {
+    pub fn bundle(exclude: Entity) -> impl Bundle {
+        (
+            Name::new("boom"),
+            ShapeCaster::new(Collider::ball(0.2), Vec3::ZERO).exclude(exclude),
         )
     }
 }
@@ -133,12 +178,12 @@ impl<'s> ShapeCast<'s> {
         }
     }
 
-    fn bundle(&self) -> impl Bundle {
+    fn bundle(&self, parameters: &Parameters) -> impl Bundle {
         (
             Name::new(self.name.to_owned()),
             ShapeCaster::new(
-                Collider::ball(COLLIDER_RADIUS - SKIN_WIDTH),
-                self.direction * CAST_DISTANCE,
+                Collider::ball(parameters.collider_radius - parameters.skin_width),
+                self.direction * parameters.cast_distance(),
             )
             .fixed_update()
             .exclude(self.exclude),
@@ -0,0 +1,33 @@
+use bevy::prelude::*;
+
+#[derive(Event, Clone, Debug)]
+pub struct Landed {
+    pub entity: Entity,
+    pub surface: Entity,
+    pub impact_speed: f32,
+}
+
+#[derive(Event, Clone, Debug)]
+pub struct Jumped {
+    pub entity: Entity,
+}
+
+#[derive(Event, Clone, Debug)]
+pub struct LeftGround {
+    pub entity: Entity,
+}
+
+#[derive(Event, Clone, Debug)]
+pub struct CrouchStarted {
+    pub entity: Entity,
+}
+
+#[derive(Event, Clone, Debug)]
+pub struct CrouchEnded {
+    pub entity: Entity,
+}
+
+#[derive(Event, Clone, Debug)]
+pub struct BlockedFromStanding {
+    pub entity: Entity,
+}
@@ -1,40 +1,50 @@
-use std::f32::consts;
 use std::time::Duration;
 
-use crate::camera_controller::CameraController;
-use crate::control::{Control, ControlSystems, Input};
+use crate::camera_controller::{CameraBoom, CameraControllerSystems};
+use crate::climbable::Climbable;
+use crate::control::Input;
 use crate::despawn::Despawn;
+use crate::gravity::Gravity;
+use crate::health::{Damage, DamageKind};
 use crate::library::{move_toward, Spawnable};
 use crate::linker::Linker;
 use crate::ray_caster::RayCasterSystems;
 use crate::shape_caster::{ShapeCaster, ShapeCasterSystems};
+use crate::water::Water;
 
 use bevy::animation::{animated_field, AnimationTargetId};
 use bevy_rapier3d::dynamics::Velocity;
 
-use bevy_rapier3d::plugin::{RapierConfiguration, RapierContext};
-use bevy_rapier3d::prelude::{Collider, GravityScale, QueryFilter};
+use bevy_rapier3d::plugin::RapierContext;
+use bevy_rapier3d::prelude::{
+    Collider, GravityScale, Group, QueryFilter, ReadMassProperties, RigidBody, ShapeCastOptions,
+    SolverGroups,
+};
 
 use bevy::prelude::*;
-use components::{Parameters, Status};
-use entities::{Head, PlayerCamera, RayCast, ShapeCast};
+pub use carrying::{Carrier, Held};
+pub use components::{Parameters, Status};
+use entities::{Head, RayCast, ShapeCast};
+pub use events::{BlockedFromStanding, CrouchEnded, CrouchStarted, Jumped, Landed, LeftGround};
+mod carrying;
 mod components;
 mod entities;
-pub use entities::Player;
+mod events;
+mod tuning;
+pub use entities::{LocalPlayer, Player, PlayerCamera};
+pub use tuning::{
+    DefaultTuning, PlayerTuning, PlayerTuningLoader, Tuning, TuningError, UsesDefaultTuning,
+};
 
 use super::fireball::Fireball;
 
-const MAX_SLOPE_ANGLE: f32 = consts::PI / 3.8;
 const HAND_DISTANCE: f32 = 2.0;
 const COLLIDER_TRANSITION_SPEED: f32 = 0.1;
-const COLLIDER_RADIUS: f32 = 0.3;
-const MAX_SURFACE_GAP: f32 = 0.03 + SKIN_WIDTH;
-const SKIN_WIDTH: f32 = 0.05;
-const COLLIDER_HALF_HEIGHT: f32 = 1.0 - COLLIDER_RADIUS;
-const COLLIDER_CROUCHING_HALF_HEIGHT: f32 = COLLIDER_HALF_HEIGHT * 0.4;
+const HALF_HEIGHT: f32 = 1.0;
+const PLAYER_GROUP: Group = Group::GROUP_1;
 
 #[derive(SystemSet, Hash, Debug, PartialEq, Eq, Clone)]
-enum PlayerSystems {
+pub enum PlayerSystems {
     Update,
     Prepare,
     FixedUpdate,
@@ -46,28 +56,63 @@ impl Plugin for PlayerPlugin {
     fn build(&self, app: &mut App) {
         app.register_type::<Parameters>()
             .register_type::<Status>()
-            .add_systems(
-                PreUpdate,
-                (camera, fireball)
-                    .in_set(PlayerSystems::Update)
-                    .after(ControlSystems)
-                    .before(RayCasterSystems),
-            )
+            .register_type::<PlayerCamera>()
+            .register_type::<Carrier>()
+            .register_type::<Held>()
+            .register_type::<Tuning>()
+            .register_type::<UsesDefaultTuning>()
+            .add_event::<Landed>()
+            .add_event::<Jumped>()
+            .add_event::<LeftGround>()
+            .add_event::<CrouchStarted>()
+            .add_event::<CrouchEnded>()
+            .add_event::<BlockedFromStanding>()
+            .init_asset::<PlayerTuning>()
+            .init_asset_loader::<PlayerTuningLoader>()
+            .add_systems(Startup, tuning::load)
+            .add_systems(Update, (tuning::attach, tuning::apply).chain())
+            .add_systems(Update, previewing.before(CameraControllerSystems::Boom))
             .configure_sets(
                 FixedPreUpdate,
                 (PlayerSystems::Prepare, PlayerSystems::FixedUpdate)
                     .chain()
-                    .after(ShapeCasterSystems),
+                    .after(ShapeCasterSystems)
+                    .after(RayCasterSystems),
+            )
+            .add_systems(
+                FixedPreUpdate,
+                (restore, carrying::excluding).before(ShapeCasterSystems),
             )
             .add_systems(
                 FixedPreUpdate,
                 (
-                    ground_check.in_set(PlayerSystems::Prepare),
+                    (
+                        carrying::rotating,
+                        camera,
+                        carrying::grabbing,
+                        fireball,
+                        aligning,
+                        ground_check,
+                        water_check,
+                        ladder_check,
+                        ledge_check,
+                    )
+                        .chain()
+                        .in_set(PlayerSystems::Prepare),
                     (
                         collider,
                         moving,
-                        falling.run_if(|input: Res<Input>| input.moving.length() > 0.0),
+                        stepping.after(moving),
+                        smoothing.after(stepping),
+                        pushing.after(stepping),
+                        sliding,
+                        climbing,
+                        mantling,
+                        falling,
                         jumping,
+                        swimming,
+                        fall_damage,
+                        carrying::carrying,
                     )
                         .in_set(PlayerSystems::FixedUpdate),
                 ),
@@ -75,17 +120,40 @@ impl Plugin for PlayerPlugin {
     }
 }
 
+type RestoreQuery<'world, 'state, 'q> = Query<
+    'world,
+    'state,
+    (Entity, &'q Status, &'q Parameters, &'q Linker),
+    Or<(Added<Status>, Changed<Parameters>)>,
+>;
+
+fn restore(mut commands: Commands, entity_q: RestoreQuery, mut caster_q: Query<&mut ShapeCaster>) {
+    for (entity, status, parameters, linker) in entity_q.iter() {
+        commands.entity(entity).insert((
+            Collider::capsule_y(status.current_collider_height, parameters.collider_radius),
+            SolverGroups::new(PLAYER_GROUP, Group::ALL),
+        ));
+
+        for name in ["cast_up", "cast_down"] {
+            let mut caster = caster_q.get_mut(*linker.get(name).unwrap()).unwrap();
+
+            caster.collider = Collider::ball(parameters.collider_radius - parameters.skin_width);
+
+            caster.direction = caster.direction.normalize() * parameters.cast_distance();
+        }
+    }
+}
+
 fn fireball(
-    keyboard: Res<ButtonInput<KeyCode>>,
     mut commands: Commands,
-    entity_q: Query<&Linker, With<Status>>,
+    mut entity_q: Query<(&mut Input, &Linker), With<Status>>,
     camera_q: Query<&GlobalTransform>,
 ) {
-    if !keyboard.just_pressed(KeyCode::KeyE) {
-        return;
-    }
+    for (mut input, linker) in entity_q.iter_mut() {
+        if !input.firing() {
+            continue;
+        }
 
-    for linker in entity_q.iter() {
         let transform = camera_q.get(*linker.get("head").unwrap()).unwrap();
 
         let direction = transform.rotation() * Vec3::NEG_Z;
@@ -100,124 +168,569 @@ fn fireball(
     }
 }
 
+type GroundCheckQuery<'world, 'state, 'q> = Query<
+    'world,
+    'state,
+    (
+        Entity,
+        &'q Linker,
+        &'q Parameters,
+        &'q Gravity,
+        &'q mut Status,
+        &'q mut GravityScale,
+        &'q mut Transform,
+        &'q GlobalTransform,
+        &'q mut Velocity,
+    ),
+>;
+
+type PlatformQuery<'world, 'state, 'q> = Query<
+    'world,
+    'state,
+    (
+        &'q Velocity,
+        &'q GlobalTransform,
+        Option<&'q ReadMassProperties>,
+    ),
+    Without<Status>,
+>;
+
 fn ground_check(
-    mut entity_q: Query<(
-        &Linker,
-        &mut Status,
-        &mut GravityScale,
-        &mut Transform,
-        &mut Velocity,
-    )>,
+    mut landings: EventWriter<Landed>,
+    mut left_ground: EventWriter<LeftGround>,
+    mut entity_q: GroundCheckQuery,
     time: Res<Time<Fixed>>,
     caster_q: Query<&ShapeCaster, Without<Status>>,
-    config_q: Query<&RapierConfiguration, Without<ShapeCaster>>,
+    platform_q: PlatformQuery,
+    mut head_q: Query<&mut Transform, Without<Status>>,
 ) {
-    let config = config_q.get_single().unwrap();
+    for (
+        entity,
+        linker,
+        parameters,
+        gravity,
+        mut status,
+        mut gravity_scale,
+        mut transform,
+        global_transform,
+        mut velocity,
+    ) in entity_q.iter_mut()
+    {
+        let was_on_surface = status.surface.is_some();
+
+        let falling_speed = status
+            .falling_speed
+            .max((velocity.linvel - status.platform_velocity).dot(-gravity.up()));
+
+        status.falling_speed = 0.0;
 
-    for (linker, mut status, mut gravity, mut transform, mut velocity) in entity_q.iter_mut() {
         status.can_standup = true;
 
         status.surface = None;
 
-        gravity.0 = 1.0;
+        status.slope = None;
 
-        let cast_down = caster_q.get(*linker.get("cast_down").unwrap()).unwrap();
+        status.platform = None;
+
+        status.platform_velocity = Vec3::ZERO;
+
+        gravity_scale.0 = 1.0;
+
+        let surface = 'check: {
+            if status.ladder.is_some() || status.mantle.is_some() {
+                break 'check None;
+            }
+
+            let cast_down = caster_q.get(*linker.get("cast_down").unwrap()).unwrap();
+
+            let Some(cast_down_result) = cast_down.result.as_ref() else {
+                status.falling_speed = falling_speed;
+
+                break 'check None;
+            };
+
+            let normal = cast_down_result.normal;
+
+            let cast_up = caster_q.get(*linker.get("cast_up").unwrap()).unwrap();
+
+            if let Some(cast_up_result) = &cast_up.result {
+                status.can_standup =
+                    parameters.can_standup(cast_up_result.distance, cast_down_result.distance);
+            }
+
+            let gravity_direction = -gravity.up();
+
+            if normal.angle_between(-gravity_direction) > parameters.max_slope_angle {
+                let slope_gap = (cast_down_result.distance - status.current_collider_height)
+                    * normal.dot(-gravity_direction)
+                    - parameters.skin_width;
+
+                if slope_gap > parameters.max_surface_gap() {
+                    status.falling_speed = falling_speed;
+
+                    break 'check None;
+                }
+
+                if slope_gap < parameters.skin_width {
+                    gravity_scale.0 = 0.0;
+
+                    velocity.linvel = velocity.linvel.reject_from(normal);
+                }
+
+                status.slope = Some(normal);
+
+                break 'check None;
+            }
+
+            let mut ground_gap =
+                cast_down_result.distance - parameters.skin_width - status.current_collider_height;
+
+            if ground_gap > parameters.max_surface_gap() {
+                let is_rising = velocity.linvel.dot(-gravity_direction) > 0.0;
+
+                if !was_on_surface || is_rising || ground_gap > parameters.max_step_height {
+                    status.falling_speed = falling_speed;
+
+                    break 'check None;
+                }
 
-        let Some(cast_down_result) = cast_down.result.as_ref() else {
+                let drop = ground_gap - parameters.skin_width;
+
+                transform.translation += gravity_direction * drop;
+
+                let mut head_transform = head_q.get_mut(*linker.get("head").unwrap()).unwrap();
+
+                head_transform.translation.y += drop;
+
+                status.step_offset += drop;
+
+                ground_gap = parameters.skin_width;
+            }
+
+            if let Ok((platform_velocity, platform_transform, mass_properties)) =
+                platform_q.get(cast_down_result.entity)
+            {
+                let center_of_mass = platform_transform.transform_point(
+                    mass_properties
+                        .map(|mass_properties| mass_properties.local_center_of_mass)
+                        .unwrap_or_default(),
+                );
+
+                let offset = global_transform.translation() - center_of_mass;
+
+                status.platform = Some(cast_down_result.entity);
+
+                status.platform_velocity =
+                    platform_velocity.linvel + platform_velocity.angvel.cross(offset);
+
+                let yaw = platform_velocity.angvel.dot(-gravity_direction) * time.delta_secs();
+
+                transform.rotation =
+                    Quat::from_axis_angle(-gravity_direction, yaw) * transform.rotation;
+            }
+
+            if ground_gap < parameters.skin_width {
+                gravity_scale.0 = 0.0;
+
+                velocity.linvel = (velocity.linvel - status.platform_velocity).reject_from(normal)
+                    + status.platform_velocity;
+
+                transform.translation +=
+                    normal * (parameters.skin_width - ground_gap) * (time.delta_secs() / 0.5)
+            }
+
+            status.surface = Some(normal);
+
+            Some(cast_down_result.entity)
+        };
+
+        match surface {
+            Some(surface) if !was_on_surface => {
+                landings.send(Landed {
+                    entity,
+                    surface,
+                    impact_speed: falling_speed,
+                });
+            }
+            None if was_on_surface => {
+                left_ground.send(LeftGround { entity });
+            }
+            _ => {}
+        }
+    }
+}
+
+fn fall_damage(
+    mut landings: EventReader<Landed>,
+    mut damages: EventWriter<Damage>,
+    entity_q: Query<&Parameters>,
+) {
+    for event in landings.read() {
+        let Ok(parameters) = entity_q.get(event.entity) else {
             continue;
         };
 
-        let normal = cast_down_result.normal;
+        if event.impact_speed <= parameters.safe_falling_speed {
+            continue;
+        }
 
-        let cast_up = caster_q.get(*linker.get("cast_up").unwrap()).unwrap();
+        damages.send(Damage {
+            target: event.entity,
+            source: Some(event.surface),
+            amount: (event.impact_speed - parameters.safe_falling_speed) * parameters.fall_damage,
+            kind: DamageKind::Fall,
+        });
+    }
+}
 
-        if let Some(cast_up_result) = &cast_up.result {
-            if cast_up_result.distance + cast_down_result.distance < COLLIDER_HALF_HEIGHT * 2.0 {
-                status.can_standup = false;
-            }
+fn water_check(
+    rapier: Single<&RapierContext>,
+    mut entity_q: Query<(Entity, &Transform, &mut Status, &mut GravityScale)>,
+    water_q: Query<(), With<Water>>,
+) {
+    for (entity, transform, mut status, mut gravity_scale) in entity_q.iter_mut() {
+        status.water = rapier
+            .intersection_pairs_with(entity)
+            .filter(|(_, _, intersecting)| *intersecting)
+            .map(|(a, b, _)| if a == entity { b } else { a })
+            .find(|other| water_q.contains(*other));
+
+        if status.water.is_none() {
+            let is_water = |other| water_q.contains(other);
+
+            let filter = QueryFilter::default().predicate(&is_water);
+
+            rapier.intersections_with_point(transform.translation, filter, |other| {
+                status.water = Some(other);
+
+                false
+            });
         }
 
-        let gravity_direction = config.gravity.normalize();
+        if status.water.is_none() {
+            continue;
+        }
+
+        status.surface = None;
+
+        status.slope = None;
+
+        gravity_scale.0 = 0.0;
+    }
+}
+
+type LadderCheckQuery<'world, 'state, 'q> = Query<
+    'world,
+    'state,
+    (
+        Entity,
+        &'q mut Status,
+        &'q mut GravityScale,
+        &'q mut Velocity,
+        &'q Transform,
+        &'q Input,
+        &'q Gravity,
+        &'q Linker,
+        &'q Parameters,
+    ),
+>;
+
+fn ladder_check(
+    rapier: Single<&RapierContext>,
+    mut entity_q: LadderCheckQuery,
+    ladder_q: Query<&Climbable>,
+    caster_q: Query<&ShapeCaster>,
+) {
+    for (
+        entity,
+        mut status,
+        mut gravity_scale,
+        mut velocity,
+        transform,
+        input,
+        gravity,
+        linker,
+        parameters,
+    ) in entity_q.iter_mut()
+    {
+        let ladder = rapier
+            .intersection_pairs_with(entity)
+            .filter(|(_, _, intersecting)| *intersecting)
+            .map(|(a, b, _)| if a == entity { b } else { a })
+            .find(|other| ladder_q.contains(*other));
+
+        let previous = status.ladder;
+
+        let is_attaching = input.moving.y < 0.0 && !status.is_jumping;
+
+        let cast_down = caster_q.get(*linker.get("cast_down").unwrap()).unwrap();
+
+        let is_grounded = cast_down.result.as_ref().is_some_and(|result| {
+            result.distance - parameters.skin_width - status.current_collider_height
+                < parameters.max_surface_gap()
+        });
 
-        if normal.angle_between(-gravity_direction) > MAX_SLOPE_ANGLE {
+        let is_stepping_off = input.moving.y > 0.0 && is_grounded;
+
+        status.ladder = ladder.filter(|_| (previous.is_some() && !is_stepping_off) || is_attaching);
+
+        if let Some(climbable) = previous
+            .filter(|_| ladder.is_none() && input.moving.y < 0.0)
+            .and_then(|previous| ladder_q.get(previous).ok())
+        {
+            let up = gravity.up();
+
+            let forward = (transform.rotation * Vec3::NEG_Z)
+                .reject_from(up)
+                .normalize_or_zero();
+
+            velocity.linvel = (forward + up) * climbable.speed;
+        }
+
+        if status.ladder.is_none() {
+            continue;
+        }
+
+        status.surface = None;
+
+        status.slope = None;
+
+        gravity_scale.0 = 0.0;
+    }
+}
+
+type LedgeCheckQuery<'world, 'state, 'q> = Query<
+    'world,
+    'state,
+    (
+        Entity,
+        &'q Linker,
+        &'q Parameters,
+        &'q Gravity,
+        &'q Transform,
+        &'q Input,
+        &'q Carrier,
+        &'q mut Status,
+    ),
+>;
+
+fn ledge_check(
+    rapier: Single<&RapierContext>,
+    mut entity_q: LedgeCheckQuery,
+    head_q: Query<&GlobalTransform>,
+) {
+    for (entity, linker, parameters, gravity, transform, input, carrier, mut status) in
+        entity_q.iter_mut()
+    {
+        if !input.holding_jump
+            || status.mantle.is_some()
+            || status.ladder.is_some()
+            || status.water.is_some()
+        {
+            continue;
+        }
+
+        let is_free =
+            |collider| Some(rapier.collider_parent(collider).unwrap_or(collider)) != carrier.held;
+
+        let filter = QueryFilter::default()
+            .exclude_sensors()
+            .exclude_rigid_body(entity)
+            .predicate(&is_free);
+
+        let up = gravity.up();
+
+        let Some(forward) = (transform.rotation * Vec3::NEG_Z)
+            .reject_from(up)
+            .try_normalize()
+        else {
+            continue;
+        };
+
+        let head = head_q
+            .get(*linker.get("head").unwrap())
+            .unwrap()
+            .translation();
+
+        let feet = head - up * (status.current_collider_height * 2.0 + parameters.collider_radius);
+
+        let Some((_, wall)) = rapier.cast_ray_and_get_normal(
+            feet + up * parameters.min_mantle_height,
+            forward,
+            parameters.collider_radius * 2.0,
+            true,
+            filter,
+        ) else {
+            continue;
+        };
+
+        if wall.normal.angle_between(up) <= parameters.max_slope_angle {
             continue;
         }
 
-        let ground_gap = cast_down_result.distance - SKIN_WIDTH - status.current_collider_height;
+        let Some((_, ledge)) = rapier.cast_ray_and_get_normal(
+            feet + up * parameters.max_mantle_height
+                + forward * (wall.time_of_impact + parameters.collider_radius),
+            -up,
+            parameters.max_mantle_height - parameters.min_mantle_height,
+            true,
+            filter,
+        ) else {
+            continue;
+        };
 
-        if ground_gap > MAX_SURFACE_GAP {
+        if ledge.time_of_impact <= 0.0
+            || ledge.normal.angle_between(up) > parameters.max_slope_angle
+        {
             continue;
         }
 
-        if ground_gap < SKIN_WIDTH {
-            gravity.0 = 0.0;
+        let target = ledge.point
+            + up * (status.current_collider_height
+                + parameters.collider_radius
+                + parameters.skin_width);
+
+        let probe = Collider::ball(parameters.collider_radius - parameters.skin_width);
+
+        let cast = |direction: Vec3| {
+            rapier
+                .cast_shape(
+                    target,
+                    Quat::IDENTITY,
+                    direction * parameters.cast_distance(),
+                    &probe,
+                    ShapeCastOptions {
+                        max_time_of_impact: 1.0,
+                        ..default()
+                    },
+                    filter,
+                )
+                .map(|(_, hit)| hit.time_of_impact * parameters.cast_distance())
+        };
 
-            velocity.linvel = velocity.linvel.reject_from(normal);
+        if let (Some(up_distance), Some(down_distance)) = (cast(up), cast(-up)) {
+            if !parameters.can_standup(up_distance, down_distance) {
+                continue;
+            }
+        }
 
-            transform.translation += normal * (SKIN_WIDTH - ground_gap) * (time.delta_secs() / 0.5)
+        let capsule =
+            Collider::capsule_y(status.current_collider_height, parameters.collider_radius);
+
+        if rapier
+            .intersection_with_shape(target, transform.rotation, &capsule, filter)
+            .is_some()
+        {
+            continue;
         }
 
-        status.surface = Some(normal);
+        status.mantle = Some(target);
+
+        status.mantle_timer = parameters.max_mantle_time;
     }
 }
 
 fn camera(
-    mut input: ResMut<Input>,
-    mut entity_q: Query<(&mut Transform, &Linker), (With<Control>, With<Parameters>)>,
+    mut entity_q: Query<(&mut Transform, &mut Input, &mut CameraBoom, &Linker), With<Parameters>>,
     mut head_q: Query<&mut Transform, Without<Parameters>>,
 ) {
-    let looking = input.looking();
+    for (mut transform, mut input, mut boom, linker) in entity_q.iter_mut() {
+        if input.switching_camera() {
+            boom.mode = boom.mode.next();
+        }
+
+        let yaw = boom.look(input.looking());
 
-    for (mut transform, linker) in entity_q.iter_mut() {
         let mut head_transform = head_q.get_mut(*linker.get("head").unwrap()).unwrap();
 
-        let rotation = head_transform.rotation * Quat::from_rotation_x(looking.y);
+        head_transform.rotation = boom.rotation();
 
-        if (rotation * Vec3::Y).y >= 0.0 {
-            head_transform.rotation = rotation;
+        transform.rotation *= Quat::from_rotation_y(yaw);
+    }
+}
+
+fn previewing(
+    entity_q: Query<(&Input, &CameraBoom, &Linker), With<Parameters>>,
+    mut head_q: Query<&mut Transform, Without<Parameters>>,
+) {
+    for (input, boom, linker) in entity_q.iter() {
+        if input.rotating_held {
+            continue;
         }
 
-        transform.rotation *= Quat::from_rotation_y(looking.x);
+        let mut boom = boom.clone();
+
+        let yaw = boom.look(input.peek_looking());
+
+        let mut head_transform = head_q.get_mut(*linker.get("head").unwrap()).unwrap();
+
+        head_transform.rotation = Quat::from_rotation_y(yaw) * boom.rotation();
     }
 }
 
+fn aligning(time: Res<Time<Fixed>>, mut entity_q: Query<(&mut Transform, &Gravity, &Parameters)>) {
+    for (mut transform, gravity, parameters) in entity_q.iter_mut() {
+        let alignment = Quat::from_rotation_arc(transform.rotation * Vec3::Y, gravity.up());
+
+        let factor = (parameters.alignment_speed * time.delta_secs()).min(1.0);
+
+        transform.rotation = Quat::IDENTITY.slerp(alignment, factor) * transform.rotation;
+    }
+}
+
+type ColliderQuery<'world, 'state, 'q> = Query<
+    'world,
+    'state,
+    (
+        Entity,
+        &'q mut Collider,
+        &'q mut Status,
+        &'q mut Transform,
+        &'q Linker,
+        &'q Input,
+        &'q Gravity,
+        &'q Parameters,
+    ),
+>;
+
 fn collider(
-    input: Res<Input>,
     time: Res<Time<Fixed>>,
-    mut entity_q: Query<(&mut Collider, &mut Status, &mut Transform, &Linker), With<Control>>,
+    mut crouch_started: EventWriter<CrouchStarted>,
+    mut crouch_ended: EventWriter<CrouchEnded>,
+    mut blocked: EventWriter<BlockedFromStanding>,
+    mut entity_q: ColliderQuery,
     caster_q: Query<&ShapeCaster, Without<Status>>,
     mut head_q: Query<&mut Transform, Without<Status>>,
-    config_q: Query<&RapierConfiguration, Without<ShapeCaster>>,
 ) {
-    let config = config_q.get_single().unwrap();
-
-    for (mut collider, mut status, mut transform, linker) in entity_q.iter_mut() {
+    for (entity, mut collider, mut status, mut transform, linker, input, gravity, parameters) in
+        entity_q.iter_mut()
+    {
         let cast_down = caster_q.get(*linker.get("cast_down").unwrap()).unwrap();
         let cast_up = caster_q.get(*linker.get("cast_up").unwrap()).unwrap();
 
         let is_touching_ground = |height: f32| {
             if let Some(result) = cast_down.result.as_ref() {
-                let surface_gap = result.distance - SKIN_WIDTH - height;
+                let surface_gap = result.distance - parameters.skin_width - height;
 
-                return surface_gap < MAX_SURFACE_GAP;
+                return surface_gap < parameters.max_surface_gap();
             }
             false
         };
 
         let celling_gap = |height: f32| {
             if let Some(result) = cast_up.result.as_ref() {
-                let surface_gap = result.distance - SKIN_WIDTH - height;
+                let surface_gap = result.distance - parameters.skin_width - height;
 
-                return surface_gap < SKIN_WIDTH;
+                return surface_gap < parameters.skin_width;
             }
             false
         };
 
         let get_height_diff = || {
-            let mut target_height = if input.crouching {
-                COLLIDER_CROUCHING_HALF_HEIGHT
+            let mut target_height = if input.crouching && status.water.is_none() {
+                parameters.crouching_half_height()
             } else if status.can_standup {
-                COLLIDER_HALF_HEIGHT
+                parameters.collider_half_height()
             } else {
                 return 0.0;
             };
@@ -244,7 +757,7 @@ fn collider(
 
         let original_height = status.current_collider_height;
 
-        let gravity_direction = config.gravity.normalize();
+        let gravity_direction = -gravity.up();
 
         if height_diff != 0.0 {
             let mut head_transform = head_q.get_mut(*linker.get("head").unwrap()).unwrap();
@@ -253,9 +766,32 @@ fn collider(
 
             head_transform.translation += Vec3::Y * height_diff;
 
-            *collider = Collider::capsule_y(status.current_collider_height, COLLIDER_RADIUS);
+            *collider =
+                Collider::capsule_y(status.current_collider_height, parameters.collider_radius);
+        }
+
+        if original_height == parameters.collider_half_height()
+            && status.current_collider_height < parameters.collider_half_height()
+        {
+            crouch_started.send(CrouchStarted { entity });
+        }
+
+        if original_height < parameters.collider_half_height()
+            && status.current_collider_height == parameters.collider_half_height()
+        {
+            crouch_ended.send(CrouchEnded { entity });
+        }
+
+        let is_standing_blocked = !input.crouching
+            && !status.can_standup
+            && status.current_collider_height < parameters.collider_half_height();
+
+        if is_standing_blocked && !status.is_standing_blocked {
+            blocked.send(BlockedFromStanding { entity });
         }
 
+        status.is_standing_blocked = is_standing_blocked;
+
         if status.current_collider_height < original_height {
             if is_touching_ground(original_height) {
                 transform.translation -= gravity_direction * height_diff;
@@ -279,71 +815,409 @@ fn collider(
     }
 }
 
+fn target_speed(parameters: &Parameters, status: &Status, input: &Input) -> f32 {
+    if input.crouching || !status.can_standup {
+        return parameters.crouching_speed;
+    }
+
+    if input.running && input.moving.y <= 0.0 {
+        return parameters.running_speed;
+    }
+
+    parameters.walking_speed
+}
+
 fn moving(
     time: Res<Time<Fixed>>,
-    input: Res<Input>,
+    mut entity_q: Query<(&mut Velocity, &Transform, &Parameters, &Status, &Input)>,
+) {
+    for (mut velocity, transform, parameters, status, input) in entity_q.iter_mut() {
+        let Some(ground_surface) = status.surface else {
+            continue;
+        };
+
+        let speed = target_speed(parameters, status, input);
+
+        let direction = Quat::from_rotation_arc(transform.rotation * Vec3::Y, ground_surface)
+            * transform.rotation
+            * Vec3::new(input.moving.x, 0.0, input.moving.y).clamp_length_max(1.0);
+
+        let relative_velocity = velocity.linvel - status.platform_velocity;
+
+        let vertical_velocity = relative_velocity.project_onto(ground_surface);
+
+        let mut horizontal_velocity = relative_velocity - vertical_velocity;
+
+        horizontal_velocity = move_toward(
+            horizontal_velocity,
+            direction * speed,
+            parameters.standing_acceleration * time.delta_secs(),
+        );
+
+        velocity.linvel = horizontal_velocity + vertical_velocity + status.platform_velocity;
+    }
+}
+
+type SteppingQuery<'world, 'state, 'q> = Query<
+    'world,
+    'state,
+    (
+        Entity,
+        &'q mut Transform,
+        &'q mut Status,
+        &'q Velocity,
+        &'q Parameters,
+        &'q Gravity,
+        &'q Carrier,
+        &'q Linker,
+    ),
+>;
+
+fn stepping(
+    time: Res<Time<Fixed>>,
+    rapier: Single<&RapierContext>,
+    mut entity_q: SteppingQuery,
+    mut head_q: Query<&mut Transform, Without<Status>>,
+) {
+    let options = ShapeCastOptions {
+        max_time_of_impact: 1.0,
+        ..default()
+    };
+
+    for (entity, mut transform, mut status, velocity, parameters, gravity, carrier, linker) in
+        entity_q.iter_mut()
+    {
+        if status.surface.is_none() {
+            continue;
+        }
+
+        let probe = Collider::ball(parameters.collider_radius - parameters.skin_width);
+
+        let up = gravity.up();
+
+        let horizontal_velocity = (velocity.linvel - status.platform_velocity).reject_from(up);
+
+        let Some(direction) = horizontal_velocity.try_normalize() else {
+            continue;
+        };
+
+        let is_free =
+            |collider| Some(rapier.collider_parent(collider).unwrap_or(collider)) != carrier.held;
+
+        let filter = QueryFilter::default()
+            .exclude_sensors()
+            .exclude_rigid_body(entity)
+            .predicate(&is_free);
+
+        let reach = direction
+            * (parameters.skin_width * 2.0 + horizontal_velocity.length() * time.delta_secs());
+
+        let feet = transform.translation - up * status.current_collider_height;
+
+        let cast = |origin: Vec3, velocity: Vec3| {
+            rapier
+                .cast_shape(origin, Quat::IDENTITY, velocity, &probe, options, filter)
+                .map(|(_, hit)| hit)
+        };
+
+        let Some(obstacle) = cast(feet, reach).and_then(|hit| hit.details) else {
+            continue;
+        };
+
+        if obstacle.normal1.angle_between(up) <= parameters.max_slope_angle {
+            continue;
+        }
+
+        let top = feet + up * parameters.max_step_height;
+
+        if cast(top, reach).is_some() {
+            continue;
+        }
+
+        let Some(step) = cast(top + reach, -up * parameters.max_step_height) else {
+            continue;
+        };
+
+        let Some(details) = step.details else {
+            continue;
+        };
+
+        if details.normal1.angle_between(up) > parameters.max_slope_angle {
+            continue;
+        }
+
+        let height = (details.witness1 - feet).dot(up) + parameters.collider_radius;
+
+        if height <= 0.0 {
+            continue;
+        }
+
+        let lift = height + parameters.skin_width;
+
+        transform.translation += up * lift + reach;
+
+        let mut head_transform = head_q.get_mut(*linker.get("head").unwrap()).unwrap();
+
+        head_transform.translation.y -= lift;
+
+        status.step_offset -= lift;
+    }
+}
+
+fn smoothing(
+    time: Res<Time<Fixed>>,
+    mut entity_q: Query<(&mut Status, &Parameters, &Linker)>,
+    mut head_q: Query<&mut Transform, Without<Status>>,
+) {
+    for (mut status, parameters, linker) in entity_q.iter_mut() {
+        if status.step_offset == 0.0 {
+            continue;
+        }
+
+        let factor = (parameters.step_smoothing * time.delta_secs()).min(1.0);
+
+        let mut delta = -status.step_offset * factor;
+
+        if (status.step_offset + delta).abs() < 0.001 {
+            delta = -status.step_offset;
+        }
+
+        status.step_offset += delta;
+
+        let mut head_transform = head_q.get_mut(*linker.get("head").unwrap()).unwrap();
+
+        head_transform.translation.y += delta;
+    }
+}
+
+type PushingQuery<'world, 'state, 'q> = Query<
+    'world,
+    'state,
+    (
+        Entity,
+        &'q mut Velocity,
+        &'q ReadMassProperties,
+        &'q Parameters,
+        &'q Status,
+        &'q Gravity,
+        &'q Carrier,
+        &'q Input,
+    ),
+>;
+
+fn pushing(
+    time: Res<Time<Fixed>>,
+    rapier: Single<&RapierContext>,
+    mut entity_q: PushingQuery,
+    mut body_q: Query<(&RigidBody, &ReadMassProperties, &mut Velocity), Without<Status>>,
+) {
+    for (entity, mut velocity, mass_properties, parameters, status, gravity, carrier, input) in
+        entity_q.iter_mut()
+    {
+        if status.surface.is_none() {
+            continue;
+        }
+
+        let up = gravity.up();
+
+        for pair in rapier.contact_pairs_with(entity) {
+            if !pair.has_any_active_contact() {
+                continue;
+            }
+
+            let (other, sign) = if pair.collider1() == entity {
+                (pair.collider2(), 1.0)
+            } else {
+                (pair.collider1(), -1.0)
+            };
+
+            let body = rapier.collider_parent(other).unwrap_or(other);
+
+            if Some(body) == carrier.held || Some(body) == status.platform {
+                continue;
+            }
+
+            let Ok((rigid_body, body_mass_properties, mut body_velocity)) = body_q.get_mut(body)
+            else {
+                continue;
+            };
+
+            if *rigid_body != RigidBody::Dynamic {
+                continue;
+            }
+
+            let Some(normal) = pair
+                .manifolds()
+                .find(|manifold| manifold.num_points() > 0)
+                .map(|manifold| manifold.normal() * sign)
+            else {
+                continue;
+            };
+
+            if normal.angle_between(up) <= parameters.max_slope_angle
+                || normal.angle_between(-up) <= parameters.max_slope_angle
+            {
+                continue;
+            }
+
+            let Some(direction) = normal.reject_from(up).try_normalize() else {
+                continue;
+            };
+
+            let along = (velocity.linvel - status.platform_velocity).dot(direction);
+
+            if along <= 0.0 {
+                continue;
+            }
+
+            let share = mass_properties.mass / (mass_properties.mass + body_mass_properties.mass);
+
+            let limit = target_speed(parameters, status, input) * share;
+
+            if along > limit {
+                velocity.linvel -= direction
+                    * (along - limit).min(parameters.standing_acceleration * time.delta_secs());
+            }
+
+            let push = (along.min(limit) - body_velocity.linvel.dot(direction)).clamp(
+                0.0,
+                parameters.pushing_force * time.delta_secs() / body_mass_properties.mass,
+            );
+
+            body_velocity.linvel += direction * push;
+        }
+    }
+}
+
+fn sliding(
+    time: Res<Time<Fixed>>,
     mut entity_q: Query<(
         &mut Velocity,
         &Transform,
         &Parameters,
         &Status,
-        Option<&Control>,
+        &Input,
+        &Gravity,
     )>,
 ) {
-    for (mut velocity, transform, parameters, status, control) in entity_q.iter_mut() {
-        let Some(ground_surface) = status.surface else {
+    for (mut velocity, transform, parameters, status, input, gravity) in entity_q.iter_mut() {
+        let Some(slope) = status.slope else {
             continue;
         };
 
-        let mut speed = parameters.walking_speed;
+        let downhill = gravity.vector.reject_from(slope).normalize_or_zero();
 
-        let mut direction = Vec3::ZERO;
+        let mut steering = (transform.rotation
+            * Vec3::new(input.moving.x, 0.0, input.moving.y).clamp_length_max(1.0))
+        .reject_from(slope);
 
-        if control.is_some() {
-            if input.running && input.moving.y <= 0.0 {
-                speed = parameters.running_speed;
-            }
+        if steering.dot(downhill) < 0.0 {
+            steering = steering.reject_from(downhill);
+        }
 
-            if input.crouching || !status.can_standup {
-                speed = parameters.crouching_speed;
-            }
+        let normal_velocity = velocity.linvel.project_onto(slope);
+
+        let mut slope_velocity = velocity.linvel - normal_velocity;
 
-            direction = Quat::from_rotation_arc(Vec3::Y, ground_surface)
-                * transform.rotation
-                * Vec3::new(input.moving.x, 0.0, input.moving.y).normalize_or_zero();
+        slope_velocity += (downhill * parameters.sliding_acceleration
+            + steering * parameters.falling_acceleration)
+            * time.delta_secs();
+
+        slope_velocity /= 1.0 + parameters.sliding_friction * time.delta_secs();
+
+        velocity.linvel = slope_velocity + normal_velocity;
+    }
+}
+
+fn climbing(
+    mut entity_q: Query<(&mut Velocity, &Status, &Input)>,
+    ladder_q: Query<(&Climbable, &GlobalTransform)>,
+) {
+    for (mut velocity, status, input) in entity_q.iter_mut() {
+        let Some((climbable, transform)) =
+            status.ladder.and_then(|ladder| ladder_q.get(ladder).ok())
+        else {
+            continue;
         };
 
-        let vertical_velocity = velocity.linvel.project_onto(ground_surface);
+        velocity.linvel = transform.up() * -input.moving.y * climbable.speed;
+    }
+}
 
-        let mut horizontal_velocity = velocity.linvel - vertical_velocity;
+fn mantling(
+    time: Res<Time<Fixed>>,
+    mut entity_q: Query<(
+        &mut Velocity,
+        &mut GravityScale,
+        &mut Status,
+        &Transform,
+        &Parameters,
+        &Gravity,
+    )>,
+) {
+    for (mut velocity, mut gravity_scale, mut status, transform, parameters, gravity) in
+        entity_q.iter_mut()
+    {
+        let Some(target) = status.mantle else {
+            continue;
+        };
 
-        horizontal_velocity = move_toward(
-            horizontal_velocity,
-            direction * speed,
-            parameters.standing_acceleration * time.delta_secs(),
-        );
+        let offset = target - transform.translation;
 
-        velocity.linvel = horizontal_velocity + vertical_velocity;
+        if offset.length() < parameters.skin_width {
+            status.mantle = None;
+
+            velocity.linvel = Vec3::ZERO;
+
+            continue;
+        }
+
+        status.mantle_timer -= time.delta_secs();
+
+        if status.mantle_timer <= 0.0 {
+            status.mantle = None;
+
+            continue;
+        }
+
+        gravity_scale.0 = 0.0;
+
+        let rise = offset.dot(gravity.up());
+
+        velocity.linvel = if rise > parameters.skin_width {
+            gravity.up() * parameters.mantle_speed
+        } else {
+            offset.normalize() * parameters.mantle_speed
+        };
     }
 }
 
 fn falling(
     time: Res<Time<Fixed>>,
-    input: Res<Input>,
-    mut entity_q: Query<(&mut Velocity, &Transform, &Parameters, &Status), With<Control>>,
-    config_q: Query<&RapierConfiguration, Without<Status>>,
+    mut entity_q: Query<(
+        &mut Velocity,
+        &Transform,
+        &Parameters,
+        &Status,
+        &Input,
+        &Gravity,
+    )>,
 ) {
-    let config = config_q.get_single().unwrap();
-
-    for (mut velocity, transform, parameters, status) in entity_q.iter_mut() {
-        if status.surface.is_some() {
+    for (mut velocity, transform, parameters, status, input, gravity) in entity_q.iter_mut() {
+        if status.surface.is_some()
+            || status.slope.is_some()
+            || status.water.is_some()
+            || status.ladder.is_some()
+            || status.mantle.is_some()
+            || input.moving == Vec2::ZERO
+        {
             continue;
         }
 
-        let direction =
-            transform.rotation * Vec3::new(input.moving.x, 0.0, input.moving.y).normalize_or_zero();
+        let direction = transform.rotation
+            * Vec3::new(input.moving.x, 0.0, input.moving.y).clamp_length_max(1.0);
 
-        let vertical_velocity = velocity.linvel.project_onto(-config.gravity.normalize());
+        let vertical_velocity = velocity.linvel.project_onto(gravity.up());
 
         let mut horizontal_velocity = velocity.linvel - vertical_velocity;
 
@@ -365,17 +1239,66 @@ fn falling(
 }
 
 fn jumping(
-    mut input: ResMut<Input>,
-    mut entity_q: Query<(&mut Velocity, &Parameters, &Status), With<Control>>,
+    time: Res<Time<Fixed>>,
+    mut jumps: EventWriter<Jumped>,
+    mut entity_q: Query<(
+        Entity,
+        &mut Velocity,
+        &Parameters,
+        &mut Status,
+        &mut Input,
+        &Gravity,
+        &Transform,
+    )>,
 ) {
-    if !input.jumping() {
-        return;
-    }
+    for (entity, mut velocity, parameters, mut status, mut input, gravity, transform) in
+        entity_q.iter_mut()
+    {
+        if status.mantle.is_some() {
+            continue;
+        }
 
-    for (mut velocity, parameters, status) in entity_q.iter_mut() {
-        let Some(ground_surface) = status.surface else {
+        let up = gravity.up();
+
+        let rising = (velocity.linvel - status.platform_velocity).dot(up);
+
+        if status.is_jumping && (rising <= 0.0 || !input.holding_jump) {
+            if rising > 0.0 {
+                velocity.linvel -= up * rising * (1.0 - parameters.jump_release_factor);
+            }
+
+            status.is_jumping = false;
+        }
+
+        let is_supported = status.surface.or(status.slope).is_some() || status.ladder.is_some();
+
+        if is_supported && !status.is_jumping {
+            status.coyote_timer = parameters.coyote_time;
+        } else {
+            status.coyote_timer = (status.coyote_timer - time.delta_secs()).max(0.0);
+        }
+
+        if input.jumping() {
+            status.jump_buffer_timer = parameters.jump_buffer_time;
+        } else {
+            status.jump_buffer_timer = (status.jump_buffer_timer - time.delta_secs()).max(0.0);
+        }
+
+        if status.jump_buffer_timer <= 0.0 || status.coyote_timer <= 0.0 {
             continue;
-        };
+        }
+
+        let mut ground_surface = status.surface.or(status.slope).unwrap_or(up);
+
+        if status.ladder.take().is_some() {
+            ground_surface = (up + transform.rotation * Vec3::Z).normalize();
+        }
+
+        status.jump_buffer_timer = 0.0;
+
+        status.coyote_timer = 0.0;
+
+        status.is_jumping = true;
 
         let mut jump_high = parameters.standing_jump_height;
 
@@ -383,7 +1306,57 @@ fn jumping(
             jump_high = parameters.crouching_jump_height;
         }
 
-        velocity.linvel = velocity.linvel - velocity.linvel.project_onto(ground_surface)
-            + ground_surface * jump_high;
+        let relative_velocity = velocity.linvel - status.platform_velocity;
+
+        velocity.linvel = relative_velocity - relative_velocity.project_onto(ground_surface)
+            + ground_surface * jump_high
+            + status.platform_velocity;
+
+        jumps.send(Jumped { entity });
+    }
+}
+
+fn swimming(
+    time: Res<Time<Fixed>>,
+    mut entity_q: Query<(
+        &mut Velocity,
+        &Linker,
+        &Parameters,
+        &Status,
+        &Input,
+        &Gravity,
+    )>,
+    head_q: Query<&GlobalTransform>,
+    water_q: Query<&Water>,
+) {
+    for (mut velocity, linker, parameters, status, input, gravity) in entity_q.iter_mut() {
+        let Some(water) = status.water.and_then(|entity| water_q.get(entity).ok()) else {
+            continue;
+        };
+
+        let up = gravity.up();
+
+        let head_transform = head_q.get(*linker.get("head").unwrap()).unwrap();
+
+        let mut direction = head_transform.rotation()
+            * Vec3::new(input.moving.x, 0.0, input.moving.y).clamp_length_max(1.0);
+
+        if input.swimming_up {
+            direction += up;
+        }
+
+        if input.swimming_down {
+            direction -= up;
+        }
+
+        velocity.linvel = move_toward(
+            velocity.linvel,
+            direction.clamp_length_max(1.0) * parameters.swimming_speed,
+            parameters.swimming_acceleration * time.delta_secs(),
+        );
+
+        velocity.linvel += gravity.vector * (1.0 - water.buoyancy) * time.delta_secs();
+
+        velocity.linvel /= 1.0 + water.drag * time.delta_secs();
     }
 }
@@ -0,0 +1,179 @@
+use std::{error::Error, fmt, io};
+
+use bevy::{
+    asset::{io::Reader, AssetLoader, AssetPath, LoadContext},
+    ecs::{component::ComponentId, world::DeferredWorld},
+    prelude::*,
+};
+use serde::{Deserialize, Serialize};
+
+use super::Parameters;
+
+const DEFAULT_TUNING_PATH: &str = "player/default.tuning.ron";
+
+#[derive(Asset, TypePath, Clone, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct PlayerTuning(pub Parameters);
+
+#[derive(Component, Reflect, Clone)]
+#[component(on_add = resolve)]
+#[reflect(Component)]
+pub struct Tuning {
+    path: Option<AssetPath<'static>>,
+    #[reflect(ignore)]
+    handle: Handle<PlayerTuning>,
+}
+
+impl Tuning {
+    pub fn new(handle: Handle<PlayerTuning>) -> Self {
+        Self {
+            path: handle.path().cloned(),
+            handle,
+        }
+    }
+
+    pub fn handle(&self) -> &Handle<PlayerTuning> {
+        &self.handle
+    }
+}
+
+fn resolve(mut world: DeferredWorld<'_>, entity: Entity, _: ComponentId) {
+    let tuning = world.get::<Tuning>(entity).unwrap();
+
+    if tuning.handle != Handle::default() {
+        return;
+    }
+
+    let Some(path) = tuning.path.clone() else {
+        return;
+    };
+
+    let handle = world.resource::<AssetServer>().load(path);
+
+    world.get_mut::<Tuning>(entity).unwrap().handle = handle;
+}
+
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+pub struct UsesDefaultTuning;
+
+#[derive(Resource, Clone)]
+pub struct DefaultTuning(pub Handle<PlayerTuning>);
+
+#[derive(Debug)]
+pub enum TuningError {
+    Io(io::Error),
+    Ron(ron::error::SpannedError),
+    Json(serde_json::Error),
+}
+
+impl fmt::Display for TuningError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TuningError::Io(error) => write!(f, "failed to read tuning: {}", error),
+            TuningError::Ron(error) => write!(f, "failed to parse tuning: {}", error),
+            TuningError::Json(error) => write!(f, "failed to parse tuning: {}", error),
+        }
+    }
+}
+
+impl Error for TuningError {}
+
+impl From<io::Error> for TuningError {
+    fn from(error: io::Error) -> Self {
+        TuningError::Io(error)
+    }
+}
+
+impl From<ron::error::SpannedError> for TuningError {
+    fn from(error: ron::error::SpannedError) -> Self {
+        TuningError::Ron(error)
+    }
+}
+
+impl From<serde_json::Error> for TuningError {
+    fn from(error: serde_json::Error) -> Self {
+        TuningError::Json(error)
+    }
+}
+
+#[derive(Default)]
+pub struct PlayerTuningLoader;
+
+impl AssetLoader for PlayerTuningLoader {
+    type Asset = PlayerTuning;
+    type Settings = ();
+    type Error = TuningError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _: &(),
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<PlayerTuning, TuningError> {
+        let mut bytes = Vec::new();
+
+        reader.read_to_end(&mut bytes).await?;
+
+        let is_json = load_context
+            .path()
+            .extension()
+            .is_some_and(|extension| extension == "json");
+
+        if is_json {
+            return Ok(serde_json::from_slice(&bytes)?);
+        }
+
+        Ok(ron::de::from_bytes(&bytes)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["tuning.ron", "tuning.json"]
+    }
+}
+
+pub fn load(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(DefaultTuning(asset_server.load(DEFAULT_TUNING_PATH)));
+}
+
+pub fn attach(
+    mut commands: Commands,
+    default: Option<Res<DefaultTuning>>,
+    entity_q: Query<Entity, (With<UsesDefaultTuning>, Without<Tuning>)>,
+) {
+    let Some(default) = default else {
+        return;
+    };
+
+    for entity in entity_q.iter() {
+        commands
+            .entity(entity)
+            .insert(Tuning::new(default.0.clone()));
+    }
+}
+
+pub fn apply(
+    mut events: EventReader<AssetEvent<PlayerTuning>>,
+    tunings: Res<Assets<PlayerTuning>>,
+    mut entity_q: Query<(Ref<Tuning>, &mut Parameters)>,
+) {
+    let changed = events
+        .read()
+        .filter_map(|event| match event {
+            AssetEvent::Added { id } | AssetEvent::Modified { id } => Some(*id),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    for (tuning, mut parameters) in entity_q.iter_mut() {
+        if !tuning.is_changed() && !changed.contains(&tuning.handle.id()) {
+            continue;
+        }
+
+        let Some(PlayerTuning(tuned)) = tunings.get(&tuning.handle) else {
+            continue;
+        };
+
+        parameters.set_if_neq(tuned.clone());
+    }
+}
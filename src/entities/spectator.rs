@@ -0,0 +1,155 @@
+use std::f32::consts;
+
+use bevy::{
+    ecs::{
+        entity::{EntityMapper, MapEntities},
+        reflect::ReflectMapEntities,
+    },
+    prelude::*,
+};
+
+use crate::{
+    camera_controller::{self, CameraController},
+    control::{Action, Bindings, ControlSystems, Devices, Input},
+    library::Spawnable,
+};
+
+#[derive(Component, Reflect, Clone)]
+#[reflect(Component, MapEntities)]
+#[require(Transform, Input)]
+pub struct FreeFly {
+    pub speed: f32,
+    pub fast_factor: f32,
+    pub slow_factor: f32,
+    pub previous: Option<Entity>,
+}
+
+impl Default for FreeFly {
+    fn default() -> Self {
+        Self {
+            speed: 8.0,
+            fast_factor: 4.0,
+            slow_factor: 0.25,
+            previous: None,
+        }
+    }
+}
+
+impl MapEntities for FreeFly {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.previous = self.previous.map(|entity| entity_mapper.map_entity(entity));
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct Spectator(pub FreeFly);
+
+impl Spawnable for Spectator {
+    fn spawn<'a>(&self, commands: &'a mut Commands) -> EntityCommands<'a> {
+        let mut entity = commands.spawn((
+            Name::new("spectator"),
+            self.0.clone(),
+            Camera3d::default(),
+            Camera {
+                is_active: false,
+                ..default()
+            },
+        ));
+
+        let id = entity.id();
+
+        entity.insert(CameraController::new(id));
+
+        entity
+    }
+}
+
+pub struct SpectatorPlugin;
+
+impl Plugin for SpectatorPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<FreeFly>()
+            .add_systems(PreUpdate, toggling.after(ControlSystems))
+            .add_systems(Update, flying);
+    }
+}
+
+fn toggling(mut commands: Commands, devices: Devices, controls: Res<Bindings>) {
+    if controls.just_pressed(Action::FreeFly, &devices) {
+        commands.queue(toggle);
+    }
+}
+
+pub fn toggle(world: &mut World) {
+    let current = camera_controller::spectated(world);
+
+    if let Some(free_fly) = current.and_then(|entity| world.get::<FreeFly>(entity).cloned()) {
+        match free_fly
+            .previous
+            .filter(|entity| world.get_entity(*entity).is_ok())
+        {
+            Some(previous) => camera_controller::possess(world, current, previous),
+            None => {
+                camera_controller::cycle(world);
+
+                if let Some(next) = camera_controller::spectated(world) {
+                    camera_controller::possess(world, current, next);
+                }
+            }
+        }
+
+        world.entity_mut(current.unwrap()).despawn_recursive();
+
+        return;
+    }
+
+    let transform = current
+        .and_then(|entity| world.get::<CameraController>(entity))
+        .and_then(|controller| world.get::<GlobalTransform>(controller.target))
+        .map(|transform| transform.compute_transform())
+        .unwrap_or_default();
+
+    let spectator = Spectator(FreeFly {
+        previous: current,
+        ..default()
+    })
+    .spawn(&mut world.commands())
+    .insert(transform)
+    .id();
+
+    world.flush();
+
+    camera_controller::possess(world, current, spectator);
+}
+
+fn flying(time: Res<Time>, mut entity_q: Query<(&mut Transform, &mut Input, &FreeFly)>) {
+    for (mut transform, mut input, free_fly) in entity_q.iter_mut() {
+        let looking = input.looking();
+
+        let (yaw, pitch, _) = transform.rotation.to_euler(EulerRot::YXZ);
+
+        transform.rotation = Quat::from_euler(
+            EulerRot::YXZ,
+            yaw + looking.x,
+            (pitch + looking.y).clamp(-consts::FRAC_PI_2, consts::FRAC_PI_2),
+            0.0,
+        );
+
+        let mut speed = free_fly.speed;
+
+        if input.running {
+            speed *= free_fly.fast_factor;
+        }
+
+        if input.crouching {
+            speed *= free_fly.slow_factor;
+        }
+
+        let vertical = input.swimming_up as i32 - input.swimming_down as i32;
+
+        let direction = transform.rotation * Vec3::new(input.moving.x, 0.0, input.moving.y)
+            + Vec3::Y * vertical as f32;
+
+        transform.translation += direction.clamp_length_max(1.0) * speed * time.delta_secs();
+    }
+}
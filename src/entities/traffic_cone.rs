@@ -1,20 +1,29 @@
 use bevy::{
-    core::Name,
-    ecs::system::{Commands, EntityCommands},
-    transform::components::Transform,
+    ecs::{component::ComponentId, world::DeferredWorld},
+    prelude::*,
 };
-use bevy_rapier3d::prelude::{ColliderMassProperties, RigidBody};
+use bevy_rapier3d::prelude::{ColliderMassProperties, ReadMassProperties, RigidBody, Velocity};
 
-use crate::{library::Spawnable, model::Model, with_mesh::WithMesh};
+use crate::{library::Spawnable, model::Model, save::Saved};
 
+#[derive(Component, Reflect, Clone)]
+#[component(on_add = build)]
+#[reflect(Component)]
+#[require(Transform, ReadMassProperties, Velocity, Saved)]
 pub struct TrafficCone;
 
+fn build(mut world: DeferredWorld<'_>, entity: Entity, _: ComponentId) {
+    world
+        .commands()
+        .entity(entity)
+        .insert(Model::new("traffic_cone/model.glb"));
+}
+
 impl Spawnable for TrafficCone {
     fn spawn<'a>(&self, commands: &'a mut Commands) -> EntityCommands<'a> {
         commands.spawn((
             Name::new("traffic_cone"),
-            Model::new("traffic_cone/model.glb"),
-            Transform::default(),
+            self.clone(),
             RigidBody::Dynamic,
             ColliderMassProperties::Mass(3.0),
         ))
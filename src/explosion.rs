@@ -20,6 +20,8 @@ use bevy_rapier3d::{
 use crate::{
     billboard::BillboardMaterial,
     despawn::Despawn,
+    health::{Damage, DamageKind},
+    levels::LevelScoped,
     library::{fibonacci_sphere, Spawnable},
 };
 
@@ -36,10 +38,12 @@ struct ExplosionAssets {
 #[derive(Component, Reflect, Clone)]
 #[component(on_add = explode)]
 #[reflect(Component)]
+#[require(LevelScoped)]
 pub struct Explosion {
     pub radius: f32,
     pub samples: usize,
     pub power: f32,
+    pub damage: f32,
 }
 
 impl Explosion {
@@ -50,6 +54,7 @@ impl Explosion {
             radius,
             samples,
             power: radius * 200.0,
+            damage: radius * 10.0,
         }
     }
 }
@@ -61,16 +66,23 @@ impl Spawnable for Explosion {
 }
 
 fn explode(mut world: DeferredWorld<'_>, entity: Entity, _: ComponentId) {
-    let assets = world.get_resource::<ExplosionAssets>().unwrap().clone();
-
-    world
-        .commands()
-        .entity(entity)
-        .insert((
-            Name::new("explosion"),
-            AudioPlayer::new(assets.explosion_sound),
-            Despawn::after(Duration::from_millis(5000)).recursive(),
-        ))
+    let assets = world.get_resource::<ExplosionAssets>().cloned();
+
+    let mut commands = world.commands();
+
+    let mut commands = commands.entity(entity);
+
+    commands.insert((
+        Name::new("explosion"),
+        Despawn::after(Duration::from_millis(5000)).recursive(),
+    ));
+
+    let Some(assets) = assets else {
+        return;
+    };
+
+    commands
+        .insert(AudioPlayer::new(assets.explosion_sound))
         .with_children(|commands| {
             commands.spawn((
                 EffectMaterial {
@@ -105,7 +117,10 @@ impl Plugin for ExplosionPlugin {
     fn build(&self, app: &mut App) {
         app.register_type::<Explosion>()
             .register_type::<ExplosionAssets>()
-            .add_systems(PreStartup, load)
+            .add_systems(
+                PreStartup,
+                load.run_if(resource_exists::<Assets<EffectAsset>>),
+            )
             .add_systems(FixedPreUpdate, update);
     }
 }
@@ -238,6 +253,7 @@ fn load(
 
 fn update(
     mut commands: Commands,
+    mut damages: EventWriter<Damage>,
     explosion_q: Query<(Entity, &GlobalTransform, &Explosion)>,
     rapier: Single<&RapierContext>,
     body_q: Query<(
@@ -252,6 +268,8 @@ fn update(
 
         let mut bodies = HashMap::<Entity, ExternalImpulse>::new();
 
+        let mut targets = HashMap::<Entity, f32>::new();
+
         for direction in fibonacci_sphere(explosion.samples) {
             let Some((collider, intersection)) = rapier.cast_ray_and_get_normal(
                 transform.translation(),
@@ -265,6 +283,12 @@ fn update(
 
             let body = rapier.collider_parent(collider).unwrap_or(collider);
 
+            let force: f32 = 1.0 - intersection.time_of_impact;
+
+            let attenuation = targets.entry(body).or_default();
+
+            *attenuation = attenuation.max(force);
+
             let (rigid_body, transform, impulse, mass_properties) = body_q.get(body).unwrap();
 
             if *rigid_body != RigidBody::Dynamic {
@@ -275,8 +299,6 @@ fn update(
                 .map(|properties| properties.local_center_of_mass)
                 .unwrap_or_default();
 
-            let force: f32 = 1.0 - intersection.time_of_impact;
-
             let impulse = bodies.get(&body).or(impulse).cloned().unwrap_or_default()
                 + ExternalImpulse::at_point(
                     direction * (explosion.power * force / explosion.samples as f32),
@@ -290,5 +312,14 @@ fn update(
         for (body, impulse) in bodies {
             commands.entity(body).insert(impulse);
         }
+
+        for (target, attenuation) in targets {
+            damages.send(Damage {
+                target,
+                source: Some(entity),
+                amount: explosion.damage * attenuation,
+                kind: DamageKind::Explosion,
+            });
+        }
     }
 }
@@ -0,0 +1,137 @@
+use bevy::prelude::*;
+use bevy_rapier3d::{
+    plugin::{RapierConfiguration, RapierContext},
+    prelude::{GravityScale, Sensor, Velocity},
+};
+
+use crate::entities::player::PlayerSystems;
+
+#[derive(Component, Reflect, Clone)]
+#[reflect(Component)]
+pub struct Gravity {
+    pub vector: Vec3,
+}
+
+impl Gravity {
+    pub fn up(&self) -> Vec3 {
+        -self.vector.normalize_or(Vec3::NEG_Y)
+    }
+}
+
+impl Default for Gravity {
+    fn default() -> Self {
+        Self {
+            vector: Vec3::NEG_Y * 9.81,
+        }
+    }
+}
+
+#[derive(Component, Reflect, Clone)]
+#[reflect(Component)]
+#[require(Sensor)]
+pub struct GravityZone {
+    pub vector: Vec3,
+}
+
+impl Default for GravityZone {
+    fn default() -> Self {
+        Self {
+            vector: Vec3::NEG_Y * 9.81,
+        }
+    }
+}
+
+#[derive(Component, Reflect, Clone)]
+#[reflect(Component)]
+#[require(Sensor)]
+pub struct GravityAttractor {
+    pub strength: f32,
+}
+
+impl Default for GravityAttractor {
+    fn default() -> Self {
+        Self { strength: 9.81 }
+    }
+}
+
+#[derive(SystemSet, Hash, Debug, PartialEq, Eq, Clone)]
+pub enum GravitySystems {
+    Update,
+    Apply,
+}
+
+pub struct GravityPlugin;
+
+impl Plugin for GravityPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Gravity>()
+            .register_type::<GravityZone>()
+            .register_type::<GravityAttractor>()
+            .configure_sets(
+                FixedPreUpdate,
+                (
+                    GravitySystems::Update.before(PlayerSystems::Prepare),
+                    GravitySystems::Apply.after(PlayerSystems::FixedUpdate),
+                ),
+            )
+            .add_systems(
+                FixedPreUpdate,
+                (
+                    update.in_set(GravitySystems::Update),
+                    apply.in_set(GravitySystems::Apply),
+                ),
+            );
+    }
+}
+
+fn update(
+    rapier: Single<&RapierContext>,
+    config_q: Query<&RapierConfiguration>,
+    mut entity_q: Query<(Entity, &GlobalTransform, &mut Gravity)>,
+    zone_q: Query<(&GravityZone, &GlobalTransform)>,
+    attractor_q: Query<(&GravityAttractor, &GlobalTransform)>,
+) {
+    let config = config_q.get_single().unwrap();
+
+    for (entity, transform, mut gravity) in entity_q.iter_mut() {
+        let mut vector = Vec3::ZERO;
+
+        let mut is_affected = false;
+
+        for other in rapier
+            .intersection_pairs_with(entity)
+            .filter(|(_, _, intersecting)| *intersecting)
+            .map(|(a, b, _)| if a == entity { b } else { a })
+        {
+            if let Ok((zone, zone_transform)) = zone_q.get(other) {
+                vector += zone_transform.rotation() * zone.vector;
+
+                is_affected = true;
+            }
+
+            if let Ok((attractor, attractor_transform)) = attractor_q.get(other) {
+                vector += (attractor_transform.translation() - transform.translation())
+                    .normalize_or_zero()
+                    * attractor.strength;
+
+                is_affected = true;
+            }
+        }
+
+        gravity.vector = if is_affected { vector } else { config.gravity };
+    }
+}
+
+fn apply(
+    time: Res<Time<Fixed>>,
+    config_q: Query<&RapierConfiguration>,
+    mut entity_q: Query<(&Gravity, Option<&GravityScale>, &mut Velocity)>,
+) {
+    let config = config_q.get_single().unwrap();
+
+    for (gravity, scale, mut velocity) in entity_q.iter_mut() {
+        let scale = scale.map_or(1.0, |scale| scale.0);
+
+        velocity.linvel += (gravity.vector - config.gravity) * scale * time.delta_secs();
+    }
+}
@@ -0,0 +1,164 @@
+use bevy::{
+    animation::AnimationPlugin,
+    app::PluginGroupBuilder,
+    ecs::world::CommandQueue,
+    gltf::GltfPlugin,
+    input::InputPlugin,
+    prelude::*,
+    render::{mesh::MeshPlugin, primitives::Aabb},
+    scene::ScenePlugin,
+    state::app::StatesPlugin,
+    time::TimeUpdateStrategy,
+};
+
+use bevy_rapier3d::prelude::RigidBody;
+
+use crate::{
+    entities::{block::BlockBundle, player::Player},
+    library::Spawnable,
+    random::RandomPlugin,
+    GamePlugins,
+};
+
+pub struct HeadlessPlugins;
+
+impl PluginGroup for HeadlessPlugins {
+    fn build(self) -> PluginGroupBuilder {
+        PluginGroupBuilder::start::<Self>()
+            .add_group(MinimalPlugins)
+            .add(TransformPlugin)
+            .add(HierarchyPlugin)
+            .add(InputPlugin)
+            .add(StatesPlugin)
+            .add(AssetPlugin::default())
+            .add(ScenePlugin)
+            .add(ImagePlugin::default())
+            .add(MeshPlugin)
+            .add(AnimationPlugin)
+            .add(GltfPlugin::default())
+            .add(HeadlessAssetsPlugin)
+    }
+}
+
+struct HeadlessAssetsPlugin;
+
+impl Plugin for HeadlessAssetsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<StandardMaterial>()
+            .register_type::<MeshMaterial3d<StandardMaterial>>()
+            .register_type::<Visibility>()
+            .register_type::<InheritedVisibility>()
+            .register_type::<ViewVisibility>()
+            .register_type::<Aabb>();
+    }
+}
+
+#[derive(Resource)]
+struct Received<E: Event>(Vec<E>);
+
+fn record<E: Event + Clone>(mut events: EventReader<E>, mut received: ResMut<Received<E>>) {
+    received.0.extend(events.read().cloned());
+}
+
+pub struct Simulation {
+    app: App,
+}
+
+impl Simulation {
+    pub fn new() -> Self {
+        Self::seeded(0)
+    }
+
+    pub fn seeded(seed: u64) -> Self {
+        let mut app = App::new();
+
+        app.add_plugins((HeadlessPlugins, GamePlugins.set(RandomPlugin::new(seed))))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(
+                Time::<Fixed>::default().timestep(),
+            ));
+
+        app.finish();
+        app.cleanup();
+        app.update();
+
+        Self { app }
+    }
+
+    pub fn spawn(&mut self, spawnable: &(impl Spawnable + ?Sized)) -> Entity {
+        let world = self.app.world_mut();
+
+        let mut queue = CommandQueue::default();
+
+        let mut commands = Commands::new(&mut queue, world);
+
+        let entity = spawnable.spawn(&mut commands).id();
+
+        queue.apply(world);
+
+        entity
+    }
+
+    pub fn block(&mut self, size: Vec3, position: Vec3) -> Entity {
+        let block = self.spawn(&BlockBundle::new(size.x, size.y, size.z));
+
+        self.world_mut()
+            .entity_mut(block)
+            .insert((RigidBody::Fixed, Transform::from_translation(position)));
+
+        block
+    }
+
+    pub fn ground(&mut self) -> Entity {
+        self.block(Vec3::new(40.0, 1.0, 40.0), Vec3::new(0.0, -0.5, 0.0))
+    }
+
+    pub fn player(&mut self, position: Vec3) -> Entity {
+        let player = self.spawn(&Player);
+
+        self.world_mut()
+            .entity_mut(player)
+            .insert(Transform::from_translation(position));
+
+        player
+    }
+
+    pub fn listen<E: Event + Clone>(&mut self) {
+        self.app
+            .insert_resource(Received::<E>(Vec::new()))
+            .add_systems(Last, record::<E>);
+    }
+
+    pub fn received<E: Event>(&self) -> &[E] {
+        &self.world().resource::<Received<E>>().0
+    }
+
+    pub fn step(&mut self, ticks: usize) {
+        for _ in 0..ticks {
+            self.app.update();
+        }
+    }
+
+    pub fn app(&mut self) -> &mut App {
+        &mut self.app
+    }
+
+    pub fn world(&self) -> &World {
+        self.app.world()
+    }
+
+    pub fn world_mut(&mut self) -> &mut World {
+        self.app.world_mut()
+    }
+
+    pub fn get<T: Component>(&self, entity: Entity) -> &T {
+        self.world()
+            .get::<T>(entity)
+            .expect("entity doesn't exist or doesn't have the requested component")
+    }
+}
+
+impl Default for Simulation {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,184 @@
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::{LockedAxes, Velocity};
+
+use crate::{
+    control::{Control, Input},
+    despawn::Despawn,
+    entities::player::Status,
+};
+
+#[derive(Component, Reflect, Clone, Debug)]
+#[reflect(Component)]
+#[require(Death)]
+pub struct Health {
+    pub current: f32,
+    pub max: f32,
+}
+
+impl Health {
+    pub fn new(max: f32) -> Self {
+        Self { current: max, max }
+    }
+
+    pub fn is_dead(&self) -> bool {
+        self.current <= 0.0
+    }
+}
+
+#[derive(Component, Reflect, Clone, Default, Debug, PartialEq, Eq)]
+#[reflect(Component)]
+pub enum Death {
+    #[default]
+    Despawn,
+    Ragdoll,
+    Respawn,
+}
+
+#[derive(Component, Reflect, Clone, Debug)]
+#[reflect(Component)]
+pub struct SpawnPoint(pub Transform);
+
+#[derive(Component, Reflect, Clone, Default, Debug)]
+#[reflect(Component)]
+pub struct Dead;
+
+#[derive(Reflect, Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub enum DamageKind {
+    #[default]
+    Generic,
+    Explosion,
+    Fall,
+}
+
+#[derive(Event, Clone, Debug)]
+pub struct Damage {
+    pub target: Entity,
+    pub source: Option<Entity>,
+    pub amount: f32,
+    pub kind: DamageKind,
+}
+
+#[derive(Event, Clone, Debug)]
+pub struct Died {
+    pub entity: Entity,
+    pub source: Option<Entity>,
+    pub kind: DamageKind,
+}
+
+#[derive(SystemSet, Hash, Debug, PartialEq, Eq, Clone)]
+pub struct HealthSystems;
+
+pub struct HealthPlugin;
+
+impl Plugin for HealthPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Health>()
+            .register_type::<Death>()
+            .register_type::<SpawnPoint>()
+            .register_type::<Dead>()
+            .register_type::<DamageKind>()
+            .add_event::<Damage>()
+            .add_event::<Died>()
+            .add_systems(
+                FixedUpdate,
+                (remember, damage, death).chain().in_set(HealthSystems),
+            );
+    }
+}
+
+type RememberQuery<'world, 'state, 'q> =
+    Query<'world, 'state, (Entity, &'q Transform, &'q Death), (Added<Death>, Without<SpawnPoint>)>;
+
+fn remember(mut commands: Commands, entity_q: RememberQuery) {
+    for (entity, transform, death) in entity_q.iter() {
+        if *death != Death::Respawn {
+            continue;
+        }
+
+        commands.entity(entity).insert(SpawnPoint(*transform));
+    }
+}
+
+fn damage(
+    mut damages: EventReader<Damage>,
+    mut deaths: EventWriter<Died>,
+    mut health_q: Query<&mut Health, Without<Dead>>,
+) {
+    for event in damages.read() {
+        let Ok(mut health) = health_q.get_mut(event.target) else {
+            continue;
+        };
+
+        if health.is_dead() || event.amount <= 0.0 {
+            continue;
+        }
+
+        health.current = (health.current - event.amount).max(0.0);
+
+        if !health.is_dead() {
+            continue;
+        }
+
+        deaths.send(Died {
+            entity: event.target,
+            source: event.source,
+            kind: event.kind,
+        });
+    }
+}
+
+type DeathQuery<'world, 'state, 'q> = Query<
+    'world,
+    'state,
+    (
+        &'q Death,
+        &'q mut Health,
+        Option<&'q SpawnPoint>,
+        Option<&'q mut Transform>,
+        Option<&'q mut Velocity>,
+        Option<&'q mut Status>,
+    ),
+>;
+
+fn death(mut commands: Commands, mut deaths: EventReader<Died>, mut entity_q: DeathQuery) {
+    for event in deaths.read() {
+        let Ok((death, mut health, spawn_point, transform, velocity, status)) =
+            entity_q.get_mut(event.entity)
+        else {
+            continue;
+        };
+
+        match death {
+            Death::Despawn => {
+                commands
+                    .entity(event.entity)
+                    .insert(Despawn::now().recursive());
+            }
+            Death::Ragdoll => {
+                commands
+                    .entity(event.entity)
+                    .insert((Dead, Input::default()))
+                    .remove::<(LockedAxes, Control)>();
+            }
+            Death::Respawn => {
+                health.current = health.max;
+
+                if let (Some(spawn_point), Some(mut transform)) = (spawn_point, transform) {
+                    *transform = spawn_point.0;
+                }
+
+                if let Some(mut velocity) = velocity {
+                    *velocity = Velocity::zero();
+                }
+
+                if let Some(mut status) = status {
+                    *status = Status {
+                        current_collider_height: status.current_collider_height,
+                        step_offset: status.step_offset,
+                        ..default()
+                    };
+                }
+            }
+        }
+    }
+}
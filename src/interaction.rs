@@ -0,0 +1,131 @@
+use std::iter;
+
+use bevy::{
+    ecs::{
+        entity::{EntityMapper, MapEntities},
+        reflect::ReflectMapEntities,
+    },
+    prelude::*,
+};
+
+use crate::{
+    control::{ControlSystems, Input},
+    linker::Linker,
+    ray_caster::{RayCaster, RayCasterSystems},
+};
+
+#[derive(Component, Reflect, Clone, Default)]
+#[reflect(Component)]
+pub struct Interactable {
+    pub prompt: String,
+}
+
+impl Interactable {
+    pub fn new(prompt: &str) -> Self {
+        Self {
+            prompt: prompt.to_owned(),
+        }
+    }
+}
+
+#[derive(Component, Reflect, Default)]
+#[reflect(Component, MapEntities)]
+pub struct Focus {
+    pub target: Option<Entity>,
+}
+
+impl MapEntities for Focus {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.target = self.target.map(|entity| entity_mapper.map_entity(entity));
+    }
+}
+
+#[derive(Event, Clone, Debug)]
+pub struct FocusChanged {
+    pub by: Entity,
+    pub previous: Option<Entity>,
+    pub current: Option<Entity>,
+}
+
+#[derive(Event, Clone, Debug)]
+pub struct Interacted {
+    pub by: Entity,
+    pub target: Entity,
+}
+
+#[derive(SystemSet, Hash, Debug, PartialEq, Eq, Clone)]
+pub struct InteractionSystems;
+
+pub struct InteractionPlugin;
+
+impl Plugin for InteractionPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Interactable>()
+            .register_type::<Focus>()
+            .add_event::<FocusChanged>()
+            .add_event::<Interacted>()
+            .add_systems(
+                PreUpdate,
+                (focus, interact)
+                    .chain()
+                    .in_set(InteractionSystems)
+                    .after(ControlSystems)
+                    .after(RayCasterSystems),
+            );
+    }
+}
+
+fn focus(
+    mut events: EventWriter<FocusChanged>,
+    mut entity_q: Query<(Entity, &Linker, &mut Focus)>,
+    caster_q: Query<&RayCaster>,
+    interactable_q: Query<(), With<Interactable>>,
+    parent_q: Query<&Parent>,
+) {
+    for (entity, linker, mut focus) in entity_q.iter_mut() {
+        let Some(ray_cast) = linker.get("ray_cast") else {
+            continue;
+        };
+
+        let hit = caster_q
+            .get(*ray_cast)
+            .ok()
+            .and_then(|caster| caster.result.as_ref())
+            .map(|result| result.entity);
+
+        let target = hit.and_then(|hit| {
+            iter::once(hit)
+                .chain(parent_q.iter_ancestors(hit))
+                .find(|candidate| interactable_q.contains(*candidate))
+        });
+
+        if focus.target == target {
+            continue;
+        }
+
+        events.send(FocusChanged {
+            by: entity,
+            previous: focus.target,
+            current: target,
+        });
+
+        focus.target = target;
+    }
+}
+
+fn interact(
+    mut events: EventWriter<Interacted>,
+    mut entity_q: Query<(Entity, &Focus, &mut Input)>,
+) {
+    for (entity, focus, mut input) in entity_q.iter_mut() {
+        if !input.interacting() {
+            continue;
+        }
+
+        let Some(target) = focus.target else {
+            continue;
+        };
+
+        events.send(Interacted { by: entity, target });
+    }
+}
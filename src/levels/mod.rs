@@ -1 +1,195 @@
+use bevy::{asset::LoadState, prelude::*};
+use test_level::TestLevelBundle;
+
+use crate::{
+    control::{Action, Bindings, Devices},
+    library::Registry,
+};
 pub mod test_level;
+
+#[derive(Resource, Deref, DerefMut)]
+pub struct Levels(Registry);
+
+impl Default for Levels {
+    fn default() -> Self {
+        Self(Registry::default().with("test_level", TestLevelBundle))
+    }
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct Level;
+
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+pub struct LevelScoped;
+
+#[derive(States, Default, Debug, Clone, PartialEq, Eq, Hash)]
+pub enum LevelState {
+    #[default]
+    Unloaded,
+    Loading,
+    Running,
+    Unloading,
+}
+
+#[derive(Event)]
+pub struct SwitchLevel(pub String);
+
+impl SwitchLevel {
+    pub fn new(name: &str) -> Self {
+        Self(name.to_owned())
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct CurrentLevel {
+    pub name: Option<String>,
+    next: Option<String>,
+}
+
+#[derive(SystemSet, Hash, Debug, PartialEq, Eq, Clone)]
+pub struct LevelSystems;
+
+pub struct LevelPlugin;
+
+impl Plugin for LevelPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Level>()
+            .register_type::<LevelScoped>()
+            .init_resource::<Levels>()
+            .init_resource::<CurrentLevel>()
+            .init_state::<LevelState>()
+            .add_event::<SwitchLevel>()
+            .add_systems(OnEnter(LevelState::Unloading), unload)
+            .add_systems(OnEnter(LevelState::Loading), load)
+            .add_systems(
+                Update,
+                (
+                    next_level,
+                    switch,
+                    ready.run_if(in_state(LevelState::Loading)),
+                )
+                    .chain()
+                    .in_set(LevelSystems),
+            );
+    }
+}
+
+fn next_level(
+    devices: Devices,
+    controls: Res<Bindings>,
+    levels: Res<Levels>,
+    current: Res<CurrentLevel>,
+    mut events: EventWriter<SwitchLevel>,
+) {
+    if !controls.just_pressed(Action::NextLevel, &devices) {
+        return;
+    }
+
+    let names = levels.names();
+
+    let index = current
+        .name
+        .as_ref()
+        .and_then(|name| names.iter().position(|other| *other == name.as_str()))
+        .map_or(0, |index| (index + 1) % names.len());
+
+    if let Some(name) = names.get(index) {
+        events.send(SwitchLevel::new(name));
+    }
+}
+
+fn switch(
+    mut events: EventReader<SwitchLevel>,
+    levels: Res<Levels>,
+    state: Res<State<LevelState>>,
+    mut next_state: ResMut<NextState<LevelState>>,
+    mut current: ResMut<CurrentLevel>,
+) {
+    let Some(SwitchLevel(name)) = events.read().last() else {
+        return;
+    };
+
+    if !levels.contains(name) {
+        error!(
+            "unknown level {}, available levels: {}",
+            name,
+            levels.names().join(", ")
+        );
+
+        return;
+    }
+
+    current.next = Some(name.clone());
+
+    if *state.get() == LevelState::Unloaded {
+        next_state.set(LevelState::Loading);
+
+        return;
+    }
+
+    next_state.set(LevelState::Unloading);
+}
+
+type RootQuery<'world, 'state> =
+    Query<'world, 'state, Entity, (Or<(With<Level>, With<LevelScoped>)>, Without<Parent>)>;
+
+fn unload(
+    mut commands: Commands,
+    mut current: ResMut<CurrentLevel>,
+    mut next_state: ResMut<NextState<LevelState>>,
+    entity_q: RootQuery,
+) {
+    for entity in entity_q.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    current.name = None;
+
+    if current.next.is_some() {
+        next_state.set(LevelState::Loading);
+
+        return;
+    }
+
+    next_state.set(LevelState::Unloaded);
+}
+
+fn load(
+    mut commands: Commands,
+    levels: Res<Levels>,
+    mut current: ResMut<CurrentLevel>,
+    mut next_state: ResMut<NextState<LevelState>>,
+) {
+    let Some(name) = current.next.take() else {
+        next_state.set(LevelState::Unloaded);
+
+        return;
+    };
+
+    levels
+        .get(&name)
+        .unwrap()
+        .spawn(&mut commands)
+        .insert(Level);
+
+    info!("loading level {}", name);
+
+    current.name = Some(name);
+}
+
+fn ready(
+    asset_server: Res<AssetServer>,
+    mut next_state: ResMut<NextState<LevelState>>,
+    scene_q: Query<&SceneRoot, With<Level>>,
+) {
+    let is_ready = scene_q.iter().all(|scene| {
+        asset_server.is_loaded_with_dependencies(&scene.0)
+            || matches!(asset_server.load_state(&scene.0), LoadState::Failed(_))
+    });
+
+    if is_ready {
+        next_state.set(LevelState::Running);
+    }
+}
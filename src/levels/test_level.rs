@@ -4,9 +4,9 @@ use bevy::prelude::*;
 use bevy_rapier3d::prelude::{RigidBody, Velocity};
 
 use crate::{
-    camera_controller::Spectate,
-    control::Control,
-    entities::{block::BlockBundle, fireball::Fireball, player::Player, traffic_cone::TrafficCone},
+    entities::{
+        block::BlockBundle, fireball::Fireball, player::LocalPlayer, traffic_cone::TrafficCone,
+    },
     library::Spawnable,
     model::Model,
 };
@@ -43,9 +43,9 @@ impl Spawnable for TestLevelBundle {
             ))
             .set_parent(entity);
 
-        Player
+        LocalPlayer
             .spawn(commands)
-            .insert((Transform::from_xyz(0.0, 3.0, 0.0), Spectate, Control))
+            .insert(Transform::from_xyz(0.0, 3.0, 0.0))
             .set_parent(entity);
 
         Fireball
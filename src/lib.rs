@@ -0,0 +1,85 @@
+use bevy::{app::PluginGroupBuilder, prelude::*};
+use bevy_rapier3d::prelude::*;
+use camera_controller::CameraControllerPlugin;
+use climbable::ClimbablePlugin;
+use control::ControlPlugin;
+use despawn::DespawnPlugin;
+use entities::{
+    fireball::FireballPlugin, player::PlayerPlugin, spectator::SpectatorPlugin, EntitiesPlugin,
+};
+use explosion::ExplosionPlugin;
+use gravity::GravityPlugin;
+use health::HealthPlugin;
+use interaction::InteractionPlugin;
+use levels::LevelPlugin;
+use linker::LinkerPlugin;
+use model::ModelPlugin;
+use random::RandomPlugin;
+use ray_caster::RayCasterPlugin;
+use replay::ReplayPlugin;
+use save::SavePlugin;
+use shape_caster::ShapeCasterPlugin;
+use throttle::ThrottlePlugin;
+use water::WaterPlugin;
+pub mod billboard;
+pub mod camera_controller;
+pub mod cli;
+pub mod climbable;
+pub mod control;
+pub mod despawn;
+pub mod entities;
+pub mod explosion;
+pub mod gravity;
+pub mod headless;
+pub mod health;
+pub mod interaction;
+pub mod levels;
+pub mod library;
+pub mod linker;
+pub mod model;
+pub mod random;
+pub mod ray_caster;
+pub mod replay;
+pub mod save;
+pub mod shape_caster;
+pub mod throttle;
+pub mod water;
+pub mod with_material;
+pub mod with_mesh;
+
+#[derive(SystemSet, Hash, Debug, PartialEq, Eq, Clone)]
+pub enum AppSystems {
+    Startup,
+    Update,
+}
+
+pub struct GamePlugins;
+
+impl PluginGroup for GamePlugins {
+    fn build(self) -> PluginGroupBuilder {
+        PluginGroupBuilder::start::<Self>()
+            .add(RapierPhysicsPlugin::<NoUserData>::default().in_fixed_schedule())
+            .add(ModelPlugin)
+            .add(EntitiesPlugin)
+            .add(PlayerPlugin)
+            .add(FireballPlugin)
+            .add(CameraControllerPlugin)
+            .add(SpectatorPlugin)
+            .add(ControlPlugin)
+            .add(ShapeCasterPlugin)
+            .add(LinkerPlugin)
+            .add(ThrottlePlugin)
+            .add(RayCasterPlugin)
+            .add(RandomPlugin::default())
+            .add(DespawnPlugin)
+            .add(ExplosionPlugin)
+            .add(WaterPlugin)
+            .add(ClimbablePlugin)
+            .add(GravityPlugin)
+            .add(HealthPlugin)
+            .add(InteractionPlugin)
+            .add(ReplayPlugin)
+            .add(SavePlugin)
+            .add(LevelPlugin)
+    }
+}
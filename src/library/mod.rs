@@ -1,6 +1,10 @@
 mod fibonacci_sphere;
 mod move_toward;
+mod registry;
 mod spawnable;
+mod spawnables;
 pub use fibonacci_sphere::fibonacci_sphere;
 pub use move_toward::move_toward;
+pub use registry::Registry;
 pub use spawnable::Spawnable;
+pub use spawnables::Spawnables;
@@ -0,0 +1,37 @@
+use std::sync::Arc;
+
+use bevy::utils::HashMap;
+
+use super::Spawnable;
+
+#[derive(Default, Clone)]
+pub struct Registry {
+    entries: HashMap<String, Arc<dyn Spawnable + Send + Sync>>,
+}
+
+impl Registry {
+    pub fn with(mut self, name: &str, spawnable: impl Spawnable + Send + Sync + 'static) -> Self {
+        self.register(name, spawnable);
+        self
+    }
+
+    pub fn register(&mut self, name: &str, spawnable: impl Spawnable + Send + Sync + 'static) {
+        self.entries.insert(name.to_owned(), Arc::new(spawnable));
+    }
+
+    pub fn get(&self, name: &str) -> Option<Arc<dyn Spawnable + Send + Sync>> {
+        self.entries.get(name).cloned()
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.entries.contains_key(name)
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.entries.keys().map(|name| name.as_str()).collect();
+
+        names.sort();
+
+        names
+    }
+}
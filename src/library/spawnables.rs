@@ -0,0 +1,6 @@
+use bevy::prelude::{Deref, DerefMut, Resource};
+
+use super::Registry;
+
+#[derive(Resource, Default, Deref, DerefMut)]
+pub struct Spawnables(Registry);
@@ -1,13 +1,17 @@
 use bevy::prelude::*;
 use bevy::{
     app::{App, Plugin},
+    ecs::{
+        entity::{EntityMapper, MapEntities},
+        reflect::ReflectMapEntities,
+    },
     prelude::{Component, Entity},
     reflect::Reflect,
     utils::hashbrown::HashMap,
 };
 
 #[derive(Component, Reflect, Default)]
-#[reflect(Component)]
+#[reflect(Component, MapEntities)]
 pub struct Linker {
     links: HashMap<String, Entity>,
 }
@@ -29,6 +33,14 @@ impl Linker {
     }
 }
 
+impl MapEntities for Linker {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        for entity in self.links.values_mut() {
+            *entity = entity_mapper.map_entity(*entity);
+        }
+    }
+}
+
 pub struct LinkerPlugin;
 
 impl Plugin for LinkerPlugin {
@@ -1,4 +1,4 @@
-use std::{env, time::Duration};
+use std::{env, process, time::Duration};
 
 use bevy::{
     color::palettes::css::RED,
@@ -6,90 +6,81 @@ use bevy::{
     pbr::NotShadowCaster,
     prelude::*,
     render::primitives::Aabb,
-    window::WindowMode,
+    window::{CursorOptions, WindowMode},
 };
-mod billboard;
-mod camera_controller;
-mod control;
-mod entities;
-mod model;
 use bevy_hanabi::HanabiPlugin;
 use bevy_inspector_egui::quick::WorldInspectorPlugin;
 use bevy_rapier3d::prelude::*;
-use billboard::BillboardPlugin;
-use camera_controller::{CameraControllerPlugin, Spectate};
-use control::{Control, ControlPlugin, Input};
-use despawn::{Despawn, DespawnPlugin};
-use entities::{
-    block::BlockBundle,
-    fireball::{Fireball, FireballPlugin},
-    player::{Player, PlayerPlugin},
+use rust_game::{
+    billboard::BillboardPlugin,
+    cli::{CliError, Options, USAGE},
+    control::{Action, Bindings, Devices, BINDINGS_PATH},
+    entities::player::Parameters,
+    headless::Simulation,
+    levels::SwitchLevel,
+    library::Spawnable,
+    random::RandomPlugin,
+    AppSystems, GamePlugins,
 };
-use explosion::ExplosionPlugin;
-use levels::test_level::TestLevelBundle;
-use library::Spawnable;
-use linker::LinkerPlugin;
-use model::ModelPlugin;
-use random::RandomPlugin;
-use ray_caster::RayCasterPlugin;
-use shape_caster::ShapeCasterPlugin;
-use throttle::ThrottlePlugin;
 use tracy_client::Client;
-use with_material::WithMaterial;
-use with_mesh::WithMesh;
-mod despawn;
-mod explosion;
-mod levels;
-mod library;
-mod linker;
-mod random;
-mod ray_caster;
-mod shape_caster;
-mod throttle;
-mod with_material;
-mod with_mesh;
-
-#[derive(SystemSet, Hash, Debug, PartialEq, Eq, Clone)]
-pub enum AppSystems {
-    Startup,
-    Update,
-}
 
 fn main() {
+    let options = match Options::parse(env::args().skip(1)) {
+        Ok(options) => options,
+        Err(CliError::Help) => {
+            println!("{}", USAGE);
+
+            return;
+        }
+        Err(error) => {
+            eprintln!("error: {}\n\n{}", error, USAGE);
+
+            process::exit(2);
+        }
+    };
+
+    if let Some(ticks) = options.headless {
+        run_headless(&options, ticks);
+
+        return;
+    }
+
     let _client = Client::start();
 
+    let game = match options.seed {
+        Some(seed) => GamePlugins.set(RandomPlugin::new(seed)),
+        None => GamePlugins.build(),
+    };
+
     let mut app = App::new();
     let mut app = app
         .add_plugins((
-            DefaultPlugins.set(ImagePlugin::default_nearest()),
-            RapierPhysicsPlugin::<NoUserData>::default(),
+            DefaultPlugins
+                .set(ImagePlugin::default_nearest())
+                .set(WindowPlugin {
+                    primary_window: Some(Window {
+                        mode: options.window_mode,
+                        cursor_options: CursorOptions {
+                            visible: options.window_mode == WindowMode::Windowed,
+                            ..default()
+                        },
+                        ..default()
+                    }),
+                    ..default()
+                }),
             HanabiPlugin,
-        ))
-        .add_plugins((
-            ModelPlugin,
-            PlayerPlugin,
-            FireballPlugin,
-            CameraControllerPlugin,
-            ControlPlugin,
-            ShapeCasterPlugin,
-            LinkerPlugin,
-            ThrottlePlugin,
-            RayCasterPlugin,
-            RandomPlugin::default(),
-            DespawnPlugin,
             BillboardPlugin,
-            ExplosionPlugin,
         ))
+        .add_plugins(game)
+        .insert_resource(Bindings::load_or_default(BINDINGS_PATH))
         .insert_resource(AmbientLight {
             color: Color::WHITE,
             brightness: 100.0,
         })
         .insert_resource(ClearColor(Color::srgb(0.8, 0.9, 1.0)))
-        .add_systems(Startup, startup.in_set(AppSystems::Startup))
         .add_systems(PreUpdate, screen_mode_update.in_set(AppSystems::Update));
 
-    #[cfg(debug_assertions)]
-    {
+    if options.debug {
         app = app.add_plugins((
             RapierDebugRenderPlugin::default(),
             WorldInspectorPlugin::default(),
@@ -106,17 +97,37 @@ fn main() {
         ));
     }
 
+    app.world_mut().send_event(SwitchLevel::new(&options.level));
+
     app.run();
 }
 
-fn screen_mode_update(mut input: ResMut<Input>, mut window: Single<&mut Window>) {
+fn run_headless(options: &Options, ticks: usize) {
+    let mut simulation = Simulation::seeded(options.seed.unwrap_or_else(rand::random));
+
+    simulation
+        .world_mut()
+        .send_event(SwitchLevel::new(&options.level));
+
+    simulation.step(ticks);
+
+    let world = simulation.world_mut();
+
+    let mut player_q = world.query_filtered::<(Entity, &GlobalTransform), With<Parameters>>();
+
+    for (entity, transform) in player_q.iter(world) {
+        println!("{}: {}", entity, transform.translation());
+    }
+}
+
+fn screen_mode_update(devices: Devices, controls: Res<Bindings>, mut window: Single<&mut Window>) {
     if let WindowMode::BorderlessFullscreen(_) = window.mode {
         let x = window.resolution.width() / 2.0;
         let y = window.resolution.height() / 2.0;
         window.set_cursor_position(Some(Vec2::new(x, y)));
     }
 
-    if !input.full_screen_switching() {
+    if !controls.just_pressed(Action::SwitchFullScreen, &devices) {
         return;
     }
 
@@ -130,7 +141,3 @@ fn screen_mode_update(mut input: ResMut<Input>, mut window: Single<&mut Window>)
     window.cursor_options.visible = false;
     window.mode = WindowMode::BorderlessFullscreen(MonitorSelection::Current)
 }
-
-fn startup(mut commands: Commands) {
-    TestLevelBundle.spawn(&mut commands);
-}
@@ -17,6 +17,8 @@ use bevy_rapier3d::{
 use serde::de::DeserializeSeed;
 use serde_json::{Deserializer, Value};
 
+use crate::library::Spawnables;
+
 pub struct Model {
     pub src: String,
 }
@@ -60,7 +62,46 @@ pub struct ModelPlugin;
 
 impl Plugin for ModelPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(First, resolve.in_set(ModelSystems::Resolve));
+        app.init_resource::<Spawnables>().add_systems(
+            First,
+            (spawn, resolve).chain().in_set(ModelSystems::Resolve),
+        );
+    }
+}
+
+fn spawn(
+    mut commands: Commands,
+    spawnables: Res<Spawnables>,
+    node_q: Query<(Entity, &GltfExtras, &Transform, Option<&Parent>)>,
+) {
+    for (entity, extras, transform, parent) in node_q.iter() {
+        let Ok(json_value) = serde_json::from_str::<Value>(&extras.value) else {
+            continue;
+        };
+
+        let Some(name) = json_value.get("spawn").and_then(|v| v.as_str()) else {
+            continue;
+        };
+
+        commands.entity(entity).despawn_recursive();
+
+        let Some(spawnable) = spawnables.get(name) else {
+            warn!(
+                "unknown spawnable {}, available spawnables: {}",
+                name,
+                spawnables.names().join(", ")
+            );
+
+            continue;
+        };
+
+        let mut spawned = spawnable.spawn(&mut commands);
+
+        spawned.insert(*transform);
+
+        if let Some(parent) = parent {
+            spawned.set_parent(parent.get());
+        }
     }
 }
 
@@ -82,6 +123,10 @@ fn resolve(
             continue;
         };
 
+        if extras.contains_key("spawn") {
+            continue;
+        }
+
         let types = types_res.read();
 
         for (component_name, value) in extras.clone() {
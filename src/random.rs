@@ -22,7 +22,7 @@ impl Default for Random {
 }
 
 impl Random {
-    fn new(seed: u64) -> Self {
+    pub fn new(seed: u64) -> Self {
         Self {
             rand: ChaCha8Rng::seed_from_u64(seed),
         }
@@ -42,7 +42,7 @@ impl Default for RandomPlugin {
 }
 
 impl RandomPlugin {
-    fn new(seed: u64) -> Self {
+    pub fn new(seed: u64) -> Self {
         Self {
             random: Some(Random::new(seed)),
         }
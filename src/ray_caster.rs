@@ -1,8 +1,11 @@
 use bevy::{
     ecs::{
         component::{ComponentHooks, ComponentId, StorageType},
+        entity::{EntityMapper, MapEntities},
+        reflect::ReflectMapEntities,
         world::DeferredWorld,
     },
+    gizmos::config::GizmoConfigStore,
     prelude::*,
 };
 use bevy_rapier3d::{
@@ -26,7 +29,7 @@ struct RayCasterFixed;
 
 #[derive(Reflect, Component)]
 #[component(on_add = insert_tag_for_fixed)]
-#[reflect(Component)]
+#[reflect(Component, MapEntities)]
 #[require(Transform)]
 pub struct RayCaster {
     pub direction: Vec3,
@@ -56,6 +59,16 @@ impl RayCaster {
     }
 }
 
+impl MapEntities for RayCaster {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.exclude = self.exclude.map(|entity| entity_mapper.map_entity(entity));
+
+        if let Some(result) = self.result.as_mut() {
+            result.entity = entity_mapper.map_entity(result.entity);
+        }
+    }
+}
+
 fn insert_tag_for_fixed(mut world: DeferredWorld<'_>, entity: Entity, _component_id: ComponentId) {
     let fixed_update = world.get::<RayCaster>(entity).unwrap().fixed_update;
 
@@ -75,11 +88,13 @@ impl Plugin for RayCasterPlugin {
                 update::<RayCasterFixed>.in_set(RayCasterSystems),
             )
             .add_systems(PreUpdate, update::<RayCaster>.in_set(RayCasterSystems));
+
+        #[cfg(debug_assertions)]
+        app.add_systems(PostUpdate, draw.run_if(resource_exists::<GizmoConfigStore>));
     }
 }
 
 fn update<T: Component>(
-    mut gizmos: Gizmos,
     rapier: Single<&RapierContext>,
     mut entity_q: Query<(&mut RayCaster, &GlobalTransform), (Without<RapierContext>, With<T>)>,
 ) {
@@ -104,40 +119,44 @@ fn update<T: Component>(
                 normal,
             });
 
-            #[cfg(not(debug_assertions))]
             continue;
+        }
 
+        caster.result = None
+    }
+}
+
+fn draw(mut gizmos: Gizmos, entity_q: Query<(&RayCaster, &GlobalTransform)>) {
+    for (caster, transform) in entity_q.iter() {
+        let Some(result) = caster.result.as_ref() else {
             gizmos.ray(
                 transform.translation(),
-                transform.rotation() * caster.direction * time_of_impact,
-                Color::linear_rgb(1.0, 0.0, 0.0),
+                transform.rotation() * caster.direction,
+                Color::linear_rgb(0.0, 0.0, 1.0),
             );
 
-            gizmos
-                .circle(
-                    Isometry3d::new(
-                        transform.translation()
-                            + transform.rotation() * caster.direction * time_of_impact
-                            + normal * 0.001,
-                        Quat::from_rotation_arc(Vec3::Z, normal),
-                    ),
-                    0.1,
-                    Color::linear_rgb(1.0, 0.0, 0.0),
-                )
-                .resolution(16);
-
             continue;
-        }
+        };
 
-        #[cfg(not(debug_assertions))]
-        continue;
+        let time_of_impact = result.distance / caster.direction.length();
 
         gizmos.ray(
             transform.translation(),
-            transform.rotation() * caster.direction,
-            Color::linear_rgb(0.0, 0.0, 1.0),
+            transform.rotation() * caster.direction * time_of_impact,
+            Color::linear_rgb(1.0, 0.0, 0.0),
         );
 
-        caster.result = None
+        gizmos
+            .circle(
+                Isometry3d::new(
+                    transform.translation()
+                        + transform.rotation() * caster.direction * time_of_impact
+                        + result.normal * 0.001,
+                    Quat::from_rotation_arc(Vec3::Z, result.normal),
+                ),
+                0.1,
+                Color::linear_rgb(1.0, 0.0, 0.0),
+            )
+            .resolution(16);
     }
 }
@@ -0,0 +1,289 @@
+use std::{error::Error, fmt, fs, io, path::Path};
+
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::{Collider, Velocity};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    camera_controller::CameraBoom,
+    control::{Action, Bindings, Control, ControlSystems, Devices, Input},
+    entities::player::{Parameters, PlayerSystems, Status},
+    linker::Linker,
+};
+
+const RECORDING_PATH: &str = "recording.json";
+
+#[derive(Debug)]
+pub enum RecordingError {
+    Io(io::Error),
+    Parse(serde_json::Error),
+}
+
+impl fmt::Display for RecordingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecordingError::Io(error) => write!(f, "failed to access recording file: {}", error),
+            RecordingError::Parse(error) => write!(f, "failed to parse recording: {}", error),
+        }
+    }
+}
+
+impl Error for RecordingError {}
+
+impl From<io::Error> for RecordingError {
+    fn from(error: io::Error) -> Self {
+        RecordingError::Io(error)
+    }
+}
+
+impl From<serde_json::Error> for RecordingError {
+    fn from(error: serde_json::Error) -> Self {
+        RecordingError::Parse(error)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct Recording {
+    pub transform: Transform,
+    pub linvel: Vec3,
+    pub angvel: Vec3,
+    pub pitch: f32,
+    pub status: Status,
+    pub ticks: Vec<Input>,
+}
+
+impl Recording {
+    pub fn new(transform: Transform, velocity: Velocity, pitch: f32, status: Status) -> Self {
+        Self {
+            transform,
+            linvel: velocity.linvel,
+            angvel: velocity.angvel,
+            pitch,
+            status,
+            ticks: Vec::new(),
+        }
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, RecordingError> {
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), RecordingError> {
+        fs::write(path, serde_json::to_string(self)?)?;
+
+        Ok(())
+    }
+}
+
+#[derive(Component)]
+pub struct Recorder {
+    recording: Recording,
+}
+
+impl Recorder {
+    pub fn new(recording: Recording) -> Self {
+        Self { recording }
+    }
+
+    pub fn recording(&self) -> &Recording {
+        &self.recording
+    }
+}
+
+#[derive(Component)]
+#[require(Input)]
+pub struct Replay {
+    recording: Recording,
+    tick: usize,
+    restore_control: bool,
+}
+
+impl Replay {
+    pub fn new(recording: Recording) -> Self {
+        Self {
+            recording,
+            tick: 0,
+            restore_control: false,
+        }
+    }
+
+    pub fn restore_control(mut self) -> Self {
+        self.restore_control = true;
+        self
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.tick >= self.recording.ticks.len()
+    }
+}
+
+#[derive(SystemSet, Hash, Debug, PartialEq, Eq, Clone)]
+pub struct ReplaySystems;
+
+pub struct ReplayPlugin;
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.configure_sets(
+            PreUpdate,
+            ReplaySystems
+                .after(ControlSystems)
+                .before(PlayerSystems::Update),
+        )
+        .configure_sets(FixedPreUpdate, ReplaySystems.before(PlayerSystems::Prepare))
+        .add_systems(PreUpdate, toggle.in_set(ReplaySystems))
+        .add_systems(FixedPreUpdate, (record, replay).in_set(ReplaySystems));
+    }
+}
+
+type ToggleQuery<'world, 'state, 'q> = Query<
+    'world,
+    'state,
+    (
+        Entity,
+        &'q Transform,
+        &'q Velocity,
+        &'q CameraBoom,
+        &'q Status,
+        Option<&'q Recorder>,
+    ),
+    With<Control>,
+>;
+
+fn toggle(
+    mut commands: Commands,
+    devices: Devices,
+    controls: Res<Bindings>,
+    entity_q: ToggleQuery,
+) {
+    if controls.just_pressed(Action::Record, &devices) {
+        for (entity, transform, velocity, boom, status, recorder) in entity_q.iter() {
+            let Some(recorder) = recorder else {
+                commands.entity(entity).insert(Recorder::new(Recording::new(
+                    *transform,
+                    *velocity,
+                    boom.pitch,
+                    status.clone(),
+                )));
+
+                info!("recording input of {}", entity);
+
+                continue;
+            };
+
+            commands.entity(entity).remove::<Recorder>();
+
+            match recorder.recording.save(RECORDING_PATH) {
+                Ok(()) => info!(
+                    "saved {} ticks to {}",
+                    recorder.recording.ticks.len(),
+                    RECORDING_PATH
+                ),
+                Err(error) => error!("{}", error),
+            }
+        }
+    }
+
+    if !controls.just_pressed(Action::Replay, &devices) {
+        return;
+    }
+
+    let recording = match Recording::load(RECORDING_PATH) {
+        Ok(recording) => recording,
+        Err(error) => {
+            error!("{}", error);
+
+            return;
+        }
+    };
+
+    for (entity, _, _, _, _, _) in entity_q.iter() {
+        commands
+            .entity(entity)
+            .remove::<(Control, Recorder)>()
+            .insert((
+                Input::default(),
+                Replay::new(recording.clone()).restore_control(),
+            ));
+    }
+}
+
+fn record(mut entity_q: Query<(&mut Recorder, &Input)>) {
+    for (mut recorder, input) in entity_q.iter_mut() {
+        recorder.recording.ticks.push(input.clone());
+    }
+}
+
+type ReplayQuery<'world, 'state, 'q> = Query<
+    'world,
+    'state,
+    (
+        Entity,
+        &'q mut Replay,
+        &'q mut Input,
+        &'q mut Transform,
+        &'q mut Velocity,
+        &'q mut Status,
+        &'q mut CameraBoom,
+        &'q mut Collider,
+        &'q Parameters,
+        &'q Linker,
+    ),
+>;
+
+fn replay(
+    mut commands: Commands,
+    mut entity_q: ReplayQuery,
+    mut head_q: Query<&mut Transform, Without<Replay>>,
+) {
+    for (
+        entity,
+        mut replay,
+        mut input,
+        mut transform,
+        mut velocity,
+        mut status,
+        mut boom,
+        mut collider,
+        parameters,
+        linker,
+    ) in entity_q.iter_mut()
+    {
+        if replay.tick == 0 {
+            *transform = replay.recording.transform;
+            *velocity = Velocity {
+                linvel: replay.recording.linvel,
+                angvel: replay.recording.angvel,
+            };
+            *status = replay.recording.status.clone();
+
+            boom.pitch = replay.recording.pitch;
+
+            *collider =
+                Collider::capsule_y(status.current_collider_height, parameters.collider_radius);
+
+            let mut head_transform = head_q.get_mut(*linker.get("head").unwrap()).unwrap();
+
+            head_transform.translation.y = status.current_collider_height + status.step_offset;
+            head_transform.rotation = boom.rotation();
+        }
+
+        if replay.is_finished() {
+            *input = Input::default();
+
+            commands.entity(entity).remove::<Replay>();
+
+            if replay.restore_control {
+                commands.entity(entity).insert(Control);
+            }
+
+            info!("replay of {} finished", entity);
+
+            continue;
+        }
+
+        *input = replay.recording.ticks[replay.tick].clone();
+
+        replay.tick += 1;
+    }
+}
@@ -0,0 +1,264 @@
+use std::{error::Error, fmt, fs, io, path::Path};
+
+use bevy::{
+    ecs::entity::EntityHashMap,
+    prelude::*,
+    scene::{serde::SceneDeserializer, SceneSpawnError},
+};
+use bevy_rapier3d::prelude::{
+    ActiveEvents, ColliderMassProperties, Friction, GravityScale, LockedAxes, RigidBody, Velocity,
+};
+use serde::de::DeserializeSeed;
+
+use crate::{
+    camera_controller::{CameraBoom, CameraController, Spectate},
+    control::{Action, Bindings, Control, ControlSystems, Devices, Input, InputSource},
+    despawn::Despawn,
+    entities::{
+        block::Block,
+        fireball::Fireball,
+        player::{Carrier, Held, Parameters, PlayerCamera, Status, Tuning, UsesDefaultTuning},
+        traffic_cone::TrafficCone,
+    },
+    health::{Dead, Death, Health, SpawnPoint},
+    interaction::{Focus, Interactable},
+    levels::LevelScoped,
+    linker::Linker,
+    ray_caster::RayCaster,
+    shape_caster::ShapeCaster,
+};
+
+const SAVE_PATH: &str = "save.ron";
+
+#[derive(Debug)]
+pub enum SaveError {
+    Io(io::Error),
+    Serialize(ron::Error),
+    Deserialize(ron::error::SpannedError),
+    Spawn(SceneSpawnError),
+}
+
+impl fmt::Display for SaveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SaveError::Io(error) => write!(f, "failed to access save file: {}", error),
+            SaveError::Serialize(error) => write!(f, "failed to serialize save: {}", error),
+            SaveError::Deserialize(error) => write!(f, "failed to parse save: {}", error),
+            SaveError::Spawn(error) => write!(f, "failed to restore save: {}", error),
+        }
+    }
+}
+
+impl Error for SaveError {}
+
+impl From<io::Error> for SaveError {
+    fn from(error: io::Error) -> Self {
+        SaveError::Io(error)
+    }
+}
+
+impl From<ron::Error> for SaveError {
+    fn from(error: ron::Error) -> Self {
+        SaveError::Serialize(error)
+    }
+}
+
+impl From<ron::error::SpannedError> for SaveError {
+    fn from(error: ron::error::SpannedError) -> Self {
+        SaveError::Deserialize(error)
+    }
+}
+
+impl From<SceneSpawnError> for SaveError {
+    fn from(error: SceneSpawnError) -> Self {
+        SaveError::Spawn(error)
+    }
+}
+
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+#[require(LevelScoped)]
+pub struct Saved;
+
+pub fn snapshot(world: &mut World) -> DynamicScene {
+    let roots = world
+        .query_filtered::<Entity, With<Saved>>()
+        .iter(world)
+        .collect::<Vec<_>>();
+
+    let mut entities = Vec::new();
+
+    for root in roots.iter() {
+        collect(world, *root, &mut entities);
+    }
+
+    let mut scene = DynamicSceneBuilder::from_world(world)
+        .deny_all()
+        .allow_component::<Name>()
+        .allow_component::<Transform>()
+        .allow_component::<Parent>()
+        .allow_component::<Children>()
+        .allow_component::<Saved>()
+        .allow_component::<RigidBody>()
+        .allow_component::<Velocity>()
+        .allow_component::<GravityScale>()
+        .allow_component::<LockedAxes>()
+        .allow_component::<Friction>()
+        .allow_component::<ColliderMassProperties>()
+        .allow_component::<ActiveEvents>()
+        .allow_component::<Parameters>()
+        .allow_component::<Status>()
+        .allow_component::<Tuning>()
+        .allow_component::<UsesDefaultTuning>()
+        .allow_component::<Input>()
+        .allow_component::<Control>()
+        .allow_component::<InputSource>()
+        .allow_component::<Spectate>()
+        .allow_component::<Linker>()
+        .allow_component::<CameraController>()
+        .allow_component::<CameraBoom>()
+        .allow_component::<PlayerCamera>()
+        .allow_component::<RayCaster>()
+        .allow_component::<ShapeCaster>()
+        .allow_component::<Despawn>()
+        .allow_component::<Fireball>()
+        .allow_component::<Block>()
+        .allow_component::<TrafficCone>()
+        .allow_component::<Health>()
+        .allow_component::<Death>()
+        .allow_component::<SpawnPoint>()
+        .allow_component::<Dead>()
+        .allow_component::<Focus>()
+        .allow_component::<Interactable>()
+        .allow_component::<Carrier>()
+        .extract_entities(entities.into_iter())
+        .build();
+
+    for entity in scene.entities.iter_mut() {
+        if world.get::<SceneRoot>(entity.entity).is_some() {
+            entity
+                .components
+                .retain(|component| !component.represents::<Children>());
+        }
+
+        if !roots.contains(&entity.entity) || world.get::<Parent>(entity.entity).is_none() {
+            continue;
+        }
+
+        entity
+            .components
+            .retain(|component| !component.represents::<Parent>());
+
+        let Some(transform) = world.get::<GlobalTransform>(entity.entity) else {
+            continue;
+        };
+
+        for component in entity.components.iter_mut() {
+            if component.represents::<Transform>() {
+                *component = Box::new(transform.compute_transform());
+            }
+        }
+    }
+
+    scene
+}
+
+fn collect(world: &World, entity: Entity, entities: &mut Vec<Entity>) {
+    entities.push(entity);
+
+    if world.get::<SceneRoot>(entity).is_some() {
+        return;
+    }
+
+    let Some(children) = world.get::<Children>(entity) else {
+        return;
+    };
+
+    for child in children.iter() {
+        collect(world, *child, entities);
+    }
+}
+
+pub fn restore(world: &mut World, scene: &DynamicScene) -> Result<(), SaveError> {
+    let held = world
+        .query_filtered::<Entity, With<Held>>()
+        .iter(world)
+        .collect::<Vec<_>>();
+
+    for entity in held {
+        world.entity_mut(entity).remove::<Held>();
+    }
+
+    world.flush();
+
+    let roots = world
+        .query_filtered::<Entity, With<Saved>>()
+        .iter(world)
+        .collect::<Vec<_>>();
+
+    for root in roots {
+        world.entity_mut(root).despawn_recursive();
+    }
+
+    scene.write_to_world(world, &mut EntityHashMap::default())?;
+
+    world.flush();
+
+    Ok(())
+}
+
+pub fn save(world: &mut World, path: impl AsRef<Path>) -> Result<(), SaveError> {
+    let scene = snapshot(world);
+
+    let registry = world.resource::<AppTypeRegistry>().read();
+
+    fs::write(path, scene.serialize(&registry)?)?;
+
+    Ok(())
+}
+
+pub fn load(world: &mut World, path: impl AsRef<Path>) -> Result<(), SaveError> {
+    let data = fs::read_to_string(path)?;
+
+    let scene = {
+        let registry = world.resource::<AppTypeRegistry>().read();
+
+        let mut deserializer = ron::de::Deserializer::from_str(&data)?;
+
+        SceneDeserializer {
+            type_registry: &registry,
+        }
+        .deserialize(&mut deserializer)
+        .map_err(|error| deserializer.span_error(error))?
+    };
+
+    restore(world, &scene)
+}
+
+#[derive(SystemSet, Hash, Debug, PartialEq, Eq, Clone)]
+pub struct SaveSystems;
+
+pub struct SavePlugin;
+
+impl Plugin for SavePlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Saved>()
+            .add_systems(PreUpdate, quick.in_set(SaveSystems).after(ControlSystems));
+    }
+}
+
+fn quick(mut commands: Commands, devices: Devices, controls: Res<Bindings>) {
+    if controls.just_pressed(Action::QuickSave, &devices) {
+        commands.queue(|world: &mut World| match save(world, SAVE_PATH) {
+            Ok(()) => info!("saved to {}", SAVE_PATH),
+            Err(error) => error!("{}", error),
+        });
+    }
+
+    if controls.just_pressed(Action::QuickLoad, &devices) {
+        commands.queue(|world: &mut World| match load(world, SAVE_PATH) {
+            Ok(()) => info!("loaded {}", SAVE_PATH),
+            Err(error) => error!("{}", error),
+        });
+    }
+}
@@ -1,6 +1,8 @@
 use bevy::{
     ecs::{
         component::{ComponentHooks, ComponentId, StorageType},
+        entity::{EntityMapper, MapEntities},
+        reflect::ReflectMapEntities,
         world::DeferredWorld,
     },
     prelude::*,
@@ -26,7 +28,7 @@ struct ShapeCasterFixed;
 
 #[derive(Reflect, Component)]
 #[component(on_add = insert_tag_for_fixed)]
-#[reflect(Component)]
+#[reflect(Component, MapEntities)]
 #[require(Transform)]
 pub struct ShapeCaster {
     #[reflect(ignore)]
@@ -34,6 +36,7 @@ pub struct ShapeCaster {
     pub direction: Vec3,
     pub result: Option<CasterResult>,
     pub fixed_update: bool,
+    pub exclude_body: Option<Entity>,
     exclude: Option<Entity>,
 }
 
@@ -43,6 +46,7 @@ impl ShapeCaster {
             direction,
             result: None,
             fixed_update: false,
+            exclude_body: None,
             exclude: None,
             collider,
         }
@@ -59,6 +63,19 @@ impl ShapeCaster {
     }
 }
 
+impl MapEntities for ShapeCaster {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.exclude = self.exclude.map(|entity| entity_mapper.map_entity(entity));
+        self.exclude_body = self
+            .exclude_body
+            .map(|entity| entity_mapper.map_entity(entity));
+
+        if let Some(result) = self.result.as_mut() {
+            result.entity = entity_mapper.map_entity(result.entity);
+        }
+    }
+}
+
 fn insert_tag_for_fixed(mut world: DeferredWorld<'_>, entity: Entity, _: ComponentId) {
     let fixed_update = world.get::<ShapeCaster>(entity).unwrap().fixed_update;
 
@@ -89,12 +106,13 @@ fn update<T: Component>(
 
     for (mut caster, transform) in entity_q.iter_mut() {
         filter.exclude_collider = caster.exclude;
+        filter.exclude_rigid_body = caster.exclude_body;
 
         if let Some((entity, time_of_impact, normal)) = rapier
             .cast_shape(
                 transform.translation(),
                 transform.rotation(),
-                caster.direction,
+                transform.rotation() * caster.direction,
                 &caster.collider,
                 ShapeCastOptions {
                     max_time_of_impact: 1.0,
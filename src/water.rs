@@ -0,0 +1,27 @@
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::Sensor;
+
+#[derive(Component, Reflect, Clone)]
+#[reflect(Component)]
+#[require(Sensor)]
+pub struct Water {
+    pub buoyancy: f32,
+    pub drag: f32,
+}
+
+impl Default for Water {
+    fn default() -> Self {
+        Self {
+            buoyancy: 1.0,
+            drag: 2.0,
+        }
+    }
+}
+
+pub struct WaterPlugin;
+
+impl Plugin for WaterPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Water>();
+    }
+}
@@ -0,0 +1,183 @@
+use bevy::prelude::*;
+use rust_game::{
+    camera_controller::{CameraBoom, CameraController, CameraMode},
+    control::Input,
+    headless::Simulation,
+    linker::Linker,
+};
+
+fn player(simulation: &mut Simulation) -> Entity {
+    simulation.ground();
+
+    let player = simulation.player(Vec3::Y);
+
+    simulation.step(30);
+
+    player
+}
+
+fn camera_offset(simulation: &Simulation, player: Entity) -> Vec3 {
+    let camera = simulation.get::<CameraController>(player).target;
+
+    simulation.get::<Transform>(camera).translation
+}
+
+#[test]
+fn switching_camera_blends_to_over_shoulder() {
+    let mut simulation = Simulation::new();
+
+    let player = player(&mut simulation);
+
+    assert_eq!(camera_offset(&simulation, player), Vec3::ZERO);
+
+    simulation
+        .world_mut()
+        .get_mut::<Input>(player)
+        .unwrap()
+        .switch_camera();
+
+    simulation.step(2);
+
+    let boom = simulation.get::<CameraBoom>(player);
+
+    assert_eq!(boom.mode, CameraMode::OverShoulder);
+
+    let shoulder = boom.shoulder;
+
+    let offset = camera_offset(&simulation, player);
+
+    assert!(
+        offset.length() > 0.0 && offset.length() < shoulder.length() * 0.5,
+        "camera is at {}",
+        offset
+    );
+
+    simulation.step(120);
+
+    let offset = camera_offset(&simulation, player);
+
+    assert!(offset.distance(shoulder) < 0.01, "camera is at {}", offset);
+}
+
+#[test]
+fn wall_pulls_over_shoulder_camera_in() {
+    let mut simulation = Simulation::new();
+
+    let player = player(&mut simulation);
+
+    simulation.block(Vec3::new(4.0, 4.0, 1.0), Vec3::new(0.0, 2.0, 2.0));
+
+    simulation
+        .world_mut()
+        .get_mut::<CameraBoom>(player)
+        .unwrap()
+        .mode = CameraMode::OverShoulder;
+
+    simulation.step(120);
+
+    let camera = simulation.get::<CameraController>(player).target;
+
+    let position = simulation.get::<GlobalTransform>(camera).translation();
+
+    assert!(position.z < 1.5, "camera is at {}", position);
+    assert!(position.z > 0.0, "camera is at {}", position);
+}
+
+#[test]
+fn pitch_is_limited() {
+    let mut simulation = Simulation::new();
+
+    let player = player(&mut simulation);
+
+    simulation
+        .world_mut()
+        .get_mut::<CameraBoom>(player)
+        .unwrap()
+        .max_pitch = 0.5;
+
+    simulation
+        .world_mut()
+        .get_mut::<Input>(player)
+        .unwrap()
+        .look(Vec2::new(0.0, 3.0));
+
+    simulation.step(1);
+
+    assert_eq!(simulation.get::<CameraBoom>(player).pitch, 0.5);
+
+    let head = *simulation.get::<Linker>(player).get("head").unwrap();
+
+    let rotation = simulation.get::<Transform>(head).rotation;
+
+    assert!(
+        rotation.angle_between(Quat::from_rotation_x(0.5)) < 0.001,
+        "head is rotated by {}",
+        rotation
+    );
+}
+
+#[test]
+fn orbit_camera_yaw_does_not_turn_player() {
+    let mut simulation = Simulation::new();
+
+    let player = player(&mut simulation);
+
+    let mut boom = simulation
+        .world_mut()
+        .get_mut::<CameraBoom>(player)
+        .unwrap();
+
+    boom.mode = CameraMode::Orbit;
+    boom.max_yaw = 1.0;
+
+    let rotation = simulation.get::<Transform>(player).rotation;
+
+    simulation
+        .world_mut()
+        .get_mut::<Input>(player)
+        .unwrap()
+        .look(Vec2::new(2.0, 0.0));
+
+    simulation.step(1);
+
+    assert_eq!(simulation.get::<CameraBoom>(player).yaw, 1.0);
+
+    let turned = simulation.get::<Transform>(player).rotation;
+
+    assert!(rotation.angle_between(turned) < 0.001);
+}
+
+#[test]
+fn looking_is_previewed_between_fixed_ticks() {
+    let mut simulation = Simulation::new();
+
+    let player = player(&mut simulation);
+
+    let head = *simulation.get::<Linker>(player).get("head").unwrap();
+
+    let rotation = simulation.get::<Transform>(player).rotation;
+
+    simulation
+        .world_mut()
+        .get_mut::<Input>(player)
+        .unwrap()
+        .look(Vec2::new(0.3, 0.2));
+
+    simulation.world_mut().run_schedule(Update);
+
+    assert_eq!(simulation.get::<Transform>(player).rotation, rotation);
+
+    let previewed = rotation * simulation.get::<Transform>(head).rotation;
+
+    simulation.step(1);
+
+    let looked =
+        simulation.get::<Transform>(player).rotation * simulation.get::<Transform>(head).rotation;
+
+    assert!(previewed.angle_between(rotation) > 0.3);
+    assert!(
+        looked.angle_between(previewed) < 0.001,
+        "head turned by {} after the fixed tick",
+        looked.angle_between(previewed)
+    );
+}
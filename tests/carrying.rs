@@ -0,0 +1,272 @@
+use std::f32::consts;
+
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::{Group, ReadMassProperties, SolverGroups, Velocity};
+use rust_game::{
+    camera_controller::CameraBoom,
+    control::Input,
+    entities::{
+        block::BlockBundle,
+        player::{Carrier, Held, Status},
+        traffic_cone::TrafficCone,
+    },
+    headless::Simulation,
+    library::Spawnable,
+    replay::{Recorder, Recording, Replay},
+};
+
+fn setup(simulation: &mut Simulation, mass: f32) -> (Entity, Entity) {
+    let block = BlockBundle::new(0.5, 0.5, 0.5).with_mass(mass);
+
+    setup_with(simulation, &block, Vec3::new(0.0, 0.25, -1.2))
+}
+
+fn setup_with(
+    simulation: &mut Simulation,
+    spawnable: &impl Spawnable,
+    position: Vec3,
+) -> (Entity, Entity) {
+    simulation.ground();
+
+    let body = simulation.spawn(spawnable);
+
+    simulation
+        .world_mut()
+        .entity_mut(body)
+        .insert(Transform::from_translation(position));
+
+    for _ in 0..1000 {
+        if simulation.get::<ReadMassProperties>(body).mass > 0.0 {
+            break;
+        }
+
+        simulation.step(1);
+    }
+
+    assert!(simulation.get::<ReadMassProperties>(body).mass > 0.0);
+
+    let player = simulation.player(Vec3::Y);
+
+    simulation.step(30);
+
+    look(simulation, player, -consts::FRAC_PI_4);
+
+    (player, body)
+}
+
+fn look(simulation: &mut Simulation, player: Entity, pitch: f32) {
+    for _ in 0..10 {
+        simulation
+            .world_mut()
+            .get_mut::<Input>(player)
+            .unwrap()
+            .look(Vec2::new(0.0, pitch / 10.0));
+
+        simulation.step(1);
+    }
+
+    simulation.step(1);
+}
+
+fn grab(simulation: &mut Simulation, player: Entity) {
+    simulation
+        .world_mut()
+        .get_mut::<Input>(player)
+        .unwrap()
+        .grab();
+
+    simulation.step(1);
+}
+
+#[test]
+fn player_carries_light_block() {
+    let mut simulation = Simulation::new();
+
+    let (player, block) = setup(&mut simulation, 3.0);
+
+    grab(&mut simulation, player);
+
+    assert_eq!(simulation.get::<Carrier>(player).held, Some(block));
+    assert_eq!(simulation.get::<Held>(block).by, player);
+    assert!(simulation.world().get::<SolverGroups>(block).is_some());
+
+    look(&mut simulation, player, consts::FRAC_PI_4);
+
+    simulation.step(60);
+
+    let position = simulation.get::<Transform>(block).translation;
+
+    assert!(
+        position.distance(Vec3::new(0.0, 1.7, -2.0)) < 0.2,
+        "block is at {}",
+        position
+    );
+
+    grab(&mut simulation, player);
+
+    assert_eq!(simulation.get::<Carrier>(player).held, None);
+    assert!(simulation.world().get::<Held>(block).is_none());
+    assert!(simulation.world().get::<SolverGroups>(block).is_none());
+}
+
+#[test]
+fn heavy_block_cannot_be_picked_up() {
+    let mut simulation = Simulation::new();
+
+    let (player, block) = setup(&mut simulation, 200.0);
+
+    grab(&mut simulation, player);
+
+    assert_eq!(simulation.get::<Carrier>(player).held, None);
+    assert!(simulation.world().get::<Held>(block).is_none());
+}
+
+#[test]
+fn thrown_block_flies_forward() {
+    let mut simulation = Simulation::new();
+
+    let (player, block) = setup(&mut simulation, 3.0);
+
+    grab(&mut simulation, player);
+
+    look(&mut simulation, player, consts::FRAC_PI_4);
+
+    simulation.step(30);
+
+    simulation
+        .world_mut()
+        .get_mut::<Input>(player)
+        .unwrap()
+        .throw();
+
+    simulation.step(2);
+
+    assert_eq!(simulation.get::<Carrier>(player).held, None);
+    assert!(simulation.world().get::<Held>(block).is_none());
+
+    let velocity = simulation.get::<Velocity>(block).linvel;
+
+    assert!(velocity.z < -5.0, "block velocity is {}", velocity);
+}
+
+#[test]
+fn rotating_held_block_keeps_camera_still() {
+    let mut simulation = Simulation::new();
+
+    let (player, block) = setup(&mut simulation, 3.0);
+
+    grab(&mut simulation, player);
+
+    let pitch = simulation.get::<CameraBoom>(player).pitch;
+
+    let rotation = simulation.get::<Held>(block).rotation;
+
+    let mut input = simulation.world_mut().get_mut::<Input>(player).unwrap();
+
+    input.rotating_held = true;
+    input.look(Vec2::new(consts::FRAC_PI_2, 0.0));
+
+    simulation.step(1);
+
+    assert_eq!(simulation.get::<CameraBoom>(player).pitch, pitch);
+
+    let turned = simulation.get::<Held>(block).rotation;
+
+    assert!((turned.angle_between(rotation) - consts::FRAC_PI_2).abs() < 0.001);
+}
+
+#[test]
+fn held_block_does_not_lift_player() {
+    let mut simulation = Simulation::new();
+
+    let (player, block) = setup(&mut simulation, 3.0);
+
+    grab(&mut simulation, player);
+
+    look(&mut simulation, player, -consts::FRAC_PI_4);
+
+    simulation.step(120);
+
+    assert_eq!(simulation.get::<Carrier>(player).held, Some(block));
+    assert!(simulation.get::<Status>(player).surface.is_some());
+
+    let position = simulation.get::<Transform>(player).translation;
+
+    assert!(position.y < 1.1, "player is at {}", position);
+}
+
+#[test]
+fn player_picks_up_traffic_cone() {
+    let mut simulation = Simulation::new();
+
+    let (player, cone) = setup_with(&mut simulation, &TrafficCone, Vec3::new(0.0, 0.0, -1.4));
+
+    grab(&mut simulation, player);
+
+    assert_eq!(simulation.get::<Carrier>(player).held, Some(cone));
+    assert_eq!(simulation.get::<Held>(cone).by, player);
+}
+
+#[test]
+fn dropping_restores_previous_solver_groups() {
+    let mut simulation = Simulation::new();
+
+    let (player, block) = setup(&mut simulation, 3.0);
+
+    let groups = SolverGroups::new(Group::GROUP_3, Group::GROUP_3 | Group::GROUP_1);
+
+    simulation.world_mut().entity_mut(block).insert(groups);
+
+    grab(&mut simulation, player);
+
+    assert_eq!(
+        *simulation.get::<SolverGroups>(block),
+        SolverGroups::new(Group::GROUP_3, Group::GROUP_3)
+    );
+
+    grab(&mut simulation, player);
+
+    assert_eq!(*simulation.get::<SolverGroups>(block), groups);
+}
+
+#[test]
+fn grabbing_is_recorded_and_replayed() {
+    let mut simulation = Simulation::new();
+
+    let (player, block) = setup(&mut simulation, 3.0);
+
+    let recording = Recording::new(
+        *simulation.get::<Transform>(player),
+        *simulation.get::<Velocity>(player),
+        simulation.get::<CameraBoom>(player).pitch,
+        simulation.get::<Status>(player).clone(),
+    );
+
+    simulation
+        .world_mut()
+        .entity_mut(player)
+        .insert(Recorder::new(recording));
+
+    grab(&mut simulation, player);
+
+    simulation.step(10);
+
+    assert_eq!(simulation.get::<Carrier>(player).held, Some(block));
+
+    let recording = simulation.get::<Recorder>(player).recording().clone();
+
+    assert!(recording.ticks.iter().any(|input| input.clone().grabbing()));
+
+    let mut simulation = Simulation::new();
+
+    let (player, block) = setup(&mut simulation, 3.0);
+
+    simulation
+        .world_mut()
+        .entity_mut(player)
+        .insert(Replay::new(recording));
+
+    simulation.step(12);
+
+    assert_eq!(simulation.get::<Carrier>(player).held, Some(block));
+}
@@ -0,0 +1,108 @@
+use bevy::window::{MonitorSelection, WindowMode};
+use rust_game::cli::{CliError, Options};
+
+fn parse(args: &[&str]) -> Result<Options, CliError> {
+    Options::parse(args.iter().map(|arg| arg.to_string()))
+}
+
+#[test]
+fn no_arguments_give_defaults() {
+    let options = parse(&[]).unwrap();
+
+    assert_eq!(options.level, "test_level");
+    assert_eq!(options.seed, None);
+    assert_eq!(options.window_mode, WindowMode::Windowed);
+    assert_eq!(options.debug, cfg!(debug_assertions));
+    assert_eq!(options.headless, None);
+}
+
+#[test]
+fn flags_combine() {
+    let options = parse(&[
+        "--level",
+        "test_level",
+        "--seed",
+        "42",
+        "--fullscreen",
+        "--no-debug",
+        "--headless",
+        "600",
+    ])
+    .unwrap();
+
+    assert_eq!(options.level, "test_level");
+    assert_eq!(options.seed, Some(42));
+    assert_eq!(
+        options.window_mode,
+        WindowMode::BorderlessFullscreen(MonitorSelection::Current)
+    );
+    assert!(!options.debug);
+    assert_eq!(options.headless, Some(600));
+}
+
+#[test]
+fn later_flags_override_earlier_ones() {
+    let options = parse(&["--fullscreen", "--windowed", "--no-debug", "--debug"]).unwrap();
+
+    assert_eq!(options.window_mode, WindowMode::Windowed);
+    assert!(options.debug);
+
+    let options = parse(&["--seed", "1", "--seed", "2"]).unwrap();
+
+    assert_eq!(options.seed, Some(2));
+}
+
+#[test]
+fn help_is_requested() {
+    assert!(matches!(parse(&["--help"]), Err(CliError::Help)));
+    assert!(matches!(parse(&["--seed", "1", "-h"]), Err(CliError::Help)));
+}
+
+#[test]
+fn unknown_argument_is_rejected() {
+    assert!(matches!(
+        parse(&["--fast"]),
+        Err(CliError::UnknownArgument(argument)) if argument == "--fast"
+    ));
+    assert!(matches!(
+        parse(&["test_level"]),
+        Err(CliError::UnknownArgument(_))
+    ));
+}
+
+#[test]
+fn missing_value_is_rejected() {
+    assert!(matches!(
+        parse(&["--level"]),
+        Err(CliError::MissingValue("--level"))
+    ));
+    assert!(matches!(
+        parse(&["--headless"]),
+        Err(CliError::MissingValue("--headless"))
+    ));
+}
+
+#[test]
+fn invalid_value_is_rejected() {
+    assert!(matches!(
+        parse(&["--seed", "-1"]),
+        Err(CliError::InvalidValue { option: "--seed", value }) if value == "-1"
+    ));
+    assert!(matches!(
+        parse(&["--headless", "forever"]),
+        Err(CliError::InvalidValue {
+            option: "--headless",
+            ..
+        })
+    ));
+}
+
+#[test]
+fn unknown_level_lists_available_levels() {
+    let Err(error) = parse(&["--level", "moon"]) else {
+        panic!("unknown level was accepted");
+    };
+
+    assert!(matches!(&error, CliError::UnknownLevel(name) if name == "moon"));
+    assert!(error.to_string().contains("test_level"));
+}
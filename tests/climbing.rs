@@ -0,0 +1,283 @@
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::{Collider, GravityScale, Velocity};
+use rust_game::{
+    climbable::Climbable, control::Input, entities::player::Status, headless::Simulation,
+};
+
+fn tower(simulation: &mut Simulation) -> Entity {
+    simulation.ground();
+
+    simulation.block(Vec3::new(4.0, 3.0, 10.0), Vec3::new(0.0, 1.5, -6.0));
+
+    simulation
+        .world_mut()
+        .spawn((
+            Climbable::default(),
+            Collider::cuboid(0.5, 1.75, 0.3),
+            Transform::from_xyz(0.0, 1.75, -0.7),
+        ))
+        .id()
+}
+
+fn climber(simulation: &mut Simulation) -> Entity {
+    let player = simulation.player(Vec3::Y);
+
+    simulation.step(30);
+
+    simulation
+        .world_mut()
+        .get_mut::<Input>(player)
+        .unwrap()
+        .moving = Vec2::NEG_Y;
+
+    player
+}
+
+#[test]
+fn player_climbs_ladder_onto_platform() {
+    let mut simulation = Simulation::new();
+
+    let ladder = tower(&mut simulation);
+
+    let player = climber(&mut simulation);
+
+    simulation.step(30);
+
+    assert_eq!(simulation.get::<Status>(player).ladder, Some(ladder));
+    assert_eq!(simulation.get::<GravityScale>(player).0, 0.0);
+
+    let height = simulation.get::<Transform>(player).translation.y;
+
+    simulation.step(30);
+
+    let climbed = simulation.get::<Transform>(player).translation.y;
+
+    assert!(climbed > height + 0.5, "player climbed to {}", climbed);
+
+    simulation.step(140);
+
+    let status = simulation.get::<Status>(player);
+
+    assert!(status.ladder.is_none());
+    assert!(status.surface.is_some());
+
+    let translation = simulation.get::<Transform>(player).translation;
+
+    assert!(
+        (translation.y - 4.0).abs() < 0.1,
+        "player ended at {}",
+        translation
+    );
+    assert!(translation.z < -1.0, "player ended at {}", translation);
+}
+
+#[test]
+fn jumping_detaches_from_ladder() {
+    let mut simulation = Simulation::new();
+
+    tower(&mut simulation);
+
+    let player = climber(&mut simulation);
+
+    simulation.step(40);
+
+    assert!(simulation.get::<Status>(player).ladder.is_some());
+
+    let mut input = simulation.world_mut().get_mut::<Input>(player).unwrap();
+
+    input.moving = Vec2::ZERO;
+    input.jump();
+
+    simulation.step(1);
+
+    assert!(simulation.get::<Status>(player).ladder.is_none());
+
+    let velocity = simulation.get::<Velocity>(player).linvel;
+
+    assert!(
+        velocity.z > 0.0 && velocity.y > 0.0,
+        "player jumped with {}",
+        velocity
+    );
+}
+
+#[test]
+fn climbing_down_to_the_ground_detaches_from_ladder() {
+    let mut simulation = Simulation::new();
+
+    let ladder = tower(&mut simulation);
+
+    let player = climber(&mut simulation);
+
+    simulation.step(40);
+
+    assert_eq!(simulation.get::<Status>(player).ladder, Some(ladder));
+
+    simulation
+        .world_mut()
+        .get_mut::<Input>(player)
+        .unwrap()
+        .moving = Vec2::Y;
+
+    simulation.step(60);
+
+    let status = simulation.get::<Status>(player);
+
+    assert!(status.ladder.is_none());
+    assert!(status.surface.is_some());
+
+    let translation = simulation.get::<Transform>(player).translation;
+
+    assert!(
+        (translation.y - 1.0).abs() < 0.1,
+        "player ended at {}",
+        translation
+    );
+    assert!(translation.z > 0.5, "player ended at {}", translation);
+}
+
+#[test]
+fn backing_away_at_the_foot_of_the_ladder_detaches() {
+    let mut simulation = Simulation::new();
+
+    tower(&mut simulation);
+
+    let player = climber(&mut simulation);
+
+    for _ in 0..60 {
+        if simulation.get::<Status>(player).ladder.is_some() {
+            break;
+        }
+
+        simulation.step(1);
+    }
+
+    assert!(simulation.get::<Status>(player).ladder.is_some());
+
+    simulation
+        .world_mut()
+        .get_mut::<Input>(player)
+        .unwrap()
+        .moving = Vec2::Y;
+
+    simulation.step(1);
+
+    assert!(simulation.get::<Status>(player).ladder.is_none());
+
+    simulation.step(30);
+
+    let status = simulation.get::<Status>(player);
+
+    assert!(status.ladder.is_none());
+    assert!(status.surface.is_some());
+
+    let translation = simulation.get::<Transform>(player).translation;
+
+    assert!(
+        (translation.y - 1.0).abs() < 0.1,
+        "player ended at {}",
+        translation
+    );
+}
+
+fn ledge(simulation: &mut Simulation, ceiling: Option<f32>) -> Entity {
+    simulation.ground();
+
+    simulation.block(Vec3::new(4.0, 1.2, 20.0), Vec3::new(0.0, 0.6, -10.5));
+
+    if let Some(height) = ceiling {
+        simulation.block(
+            Vec3::new(4.0, 1.0, 20.0),
+            Vec3::new(0.0, 1.2 + height + 0.5, -10.5),
+        );
+    }
+
+    let player = climber(simulation);
+
+    simulation
+        .world_mut()
+        .get_mut::<Input>(player)
+        .unwrap()
+        .jump();
+
+    player
+}
+
+#[test]
+fn player_mantles_onto_ledge() {
+    let mut simulation = Simulation::new();
+
+    let player = ledge(&mut simulation, None);
+
+    simulation.step(120);
+
+    let status = simulation.get::<Status>(player);
+
+    assert!(status.mantle.is_none());
+    assert!(status.surface.is_some());
+
+    let translation = simulation.get::<Transform>(player).translation;
+
+    assert!(
+        (translation.y - 2.2).abs() < 0.1,
+        "player ended at {}",
+        translation
+    );
+    assert!(translation.z < -0.5, "player ended at {}", translation);
+}
+
+#[test]
+fn mantling_respects_blocked_headroom() {
+    let mut simulation = Simulation::new();
+
+    let player = ledge(&mut simulation, Some(1.0));
+
+    simulation.step(120);
+
+    let translation = simulation.get::<Transform>(player).translation;
+
+    assert!(
+        (translation.y - 1.0).abs() < 0.1,
+        "player ended at {}",
+        translation
+    );
+    assert!(translation.z > -0.5, "player ended at {}", translation);
+}
+
+#[test]
+fn blocked_mantle_is_cancelled() {
+    let mut simulation = Simulation::new();
+
+    let player = ledge(&mut simulation, None);
+
+    for _ in 0..60 {
+        if simulation.get::<Status>(player).mantle.is_some() {
+            break;
+        }
+
+        simulation.step(1);
+    }
+
+    assert!(simulation.get::<Status>(player).mantle.is_some());
+
+    simulation
+        .world_mut()
+        .get_mut::<Input>(player)
+        .unwrap()
+        .holding_jump = false;
+
+    let position = simulation.get::<Transform>(player).translation;
+
+    simulation.block(Vec3::new(4.0, 0.2, 4.0), position + Vec3::Y * 1.2);
+
+    simulation.step(120);
+
+    let status = simulation.get::<Status>(player);
+
+    assert!(status.mantle.is_none());
+    assert!(status.surface.is_some());
+
+    let translation = simulation.get::<Transform>(player).translation;
+
+    assert!(translation.y < 1.2, "player ended at {}", translation);
+}
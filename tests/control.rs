@@ -0,0 +1,475 @@
+use std::{env, fs};
+
+use bevy::{
+    input::{
+        gamepad::{
+            GamepadConnection, GamepadConnectionEvent, RawGamepadAxisChangedEvent,
+            RawGamepadButtonChangedEvent, RawGamepadEvent,
+        },
+        keyboard::{Key, KeyboardInput, NativeKey},
+        ButtonState,
+    },
+    prelude::*,
+};
+use rust_game::{
+    camera_controller,
+    control::{Action, Binding, Bindings, BindingsError, Control, Input, InputSource, Modifier},
+    entities::fireball::Fireball,
+    headless::Simulation,
+};
+
+fn gamepad(simulation: &mut Simulation) -> Entity {
+    let gamepad = simulation.world_mut().spawn_empty().id();
+
+    simulation
+        .world_mut()
+        .send_event(GamepadConnectionEvent::new(
+            gamepad,
+            GamepadConnection::Connected {
+                name: "test".to_owned(),
+                vendor_id: None,
+                product_id: None,
+            },
+        ));
+
+    simulation.step(1);
+
+    gamepad
+}
+
+fn press(simulation: &mut Simulation, keys: &[KeyCode]) {
+    simulation
+        .world_mut()
+        .send_event_batch(keys.iter().map(|key| KeyboardInput {
+            key_code: *key,
+            logical_key: Key::Unidentified(NativeKey::Unidentified),
+            state: ButtonState::Pressed,
+            repeat: false,
+            window: Entity::PLACEHOLDER,
+        }));
+
+    simulation.step(1);
+}
+
+fn write(name: &str, contents: &str) -> std::path::PathBuf {
+    let path = env::temp_dir().join(format!("rust_game_{}.json", name));
+
+    fs::write(&path, contents).unwrap();
+
+    path
+}
+
+#[test]
+fn gamepad_drives_input() {
+    let mut simulation = Simulation::new();
+
+    let entity = simulation.world_mut().spawn(Control).id();
+
+    let gamepad = gamepad(&mut simulation);
+
+    simulation.world_mut().send_event_batch([
+        RawGamepadEvent::Axis(RawGamepadAxisChangedEvent::new(
+            gamepad,
+            GamepadAxis::LeftStickY,
+            1.0,
+        )),
+        RawGamepadEvent::Axis(RawGamepadAxisChangedEvent::new(
+            gamepad,
+            GamepadAxis::RightStickX,
+            0.5,
+        )),
+        RawGamepadEvent::Button(RawGamepadButtonChangedEvent::new(
+            gamepad,
+            GamepadButton::RightTrigger2,
+            1.0,
+        )),
+    ]);
+
+    simulation.step(1);
+
+    let input = simulation.get::<Input>(entity);
+
+    assert!(
+        input.moving.distance(Vec2::NEG_Y) < 0.001,
+        "moving is {}",
+        input.moving
+    );
+    assert!(input.running);
+    assert!(
+        input.peek_looking().x < 0.0,
+        "looking is {}",
+        input.peek_looking()
+    );
+}
+
+#[test]
+fn gamepad_stick_respects_deadzone() {
+    let mut simulation = Simulation::new();
+
+    let entity = simulation.world_mut().spawn(Control).id();
+
+    let gamepad = gamepad(&mut simulation);
+
+    let deadzone = simulation.world().resource::<Bindings>().gamepad_deadzone;
+
+    simulation
+        .world_mut()
+        .send_event(RawGamepadEvent::Axis(RawGamepadAxisChangedEvent::new(
+            gamepad,
+            GamepadAxis::LeftStickX,
+            deadzone * 0.5,
+        )));
+
+    simulation.step(1);
+
+    assert_eq!(simulation.get::<Input>(entity).moving, Vec2::ZERO);
+}
+
+#[test]
+fn invalid_sensitivity_names_the_setting() {
+    let bindings = Bindings {
+        gamepad_sensitivity: 0.0,
+        ..default()
+    };
+
+    let error = bindings.validate().unwrap_err();
+
+    assert!(matches!(
+        error,
+        BindingsError::InvalidSensitivity {
+            setting: "gamepad_sensitivity",
+            ..
+        }
+    ));
+    assert!(error.to_string().contains("gamepad_sensitivity"));
+}
+
+#[test]
+fn invalid_deadzone_is_reported() {
+    let bindings = Bindings {
+        gamepad_deadzone: 1.5,
+        ..default()
+    };
+
+    let error = bindings.validate().unwrap_err();
+
+    assert!(matches!(error, BindingsError::InvalidDeadzone(_)));
+    assert!(error.to_string().contains("deadzone"));
+}
+
+#[test]
+fn fire_key_sets_firing() {
+    let mut simulation = Simulation::new();
+
+    let entity = simulation.world_mut().spawn(Control).id();
+
+    press(&mut simulation, &[KeyCode::KeyE]);
+
+    let mut input = simulation.world_mut().get_mut::<Input>(entity).unwrap();
+
+    assert!(input.firing());
+    assert!(!input.firing());
+}
+
+#[test]
+fn firing_spawns_fireball() {
+    let mut simulation = Simulation::new();
+
+    simulation.ground();
+
+    let player = simulation.player(Vec3::Y);
+
+    simulation.step(10);
+
+    simulation
+        .world_mut()
+        .get_mut::<Input>(player)
+        .unwrap()
+        .fire();
+
+    simulation.step(1);
+
+    let fireballs = simulation
+        .world_mut()
+        .query_filtered::<Entity, With<Fireball>>()
+        .iter(simulation.world())
+        .count();
+
+    assert_eq!(fireballs, 1);
+}
+
+#[test]
+fn modified_binding_takes_precedence_over_plain_key() {
+    let mut simulation = Simulation::new();
+
+    simulation
+        .world_mut()
+        .resource_mut::<Bindings>()
+        .actions
+        .insert(
+            Action::Fire,
+            vec![Binding::key(KeyCode::KeyW).with_modifier(Modifier::Shift)],
+        );
+
+    let entity = simulation.world_mut().spawn(Control).id();
+
+    press(&mut simulation, &[KeyCode::ShiftLeft, KeyCode::KeyW]);
+
+    let mut input = simulation.world_mut().get_mut::<Input>(entity).unwrap();
+
+    assert_eq!(input.moving, Vec2::ZERO);
+    assert!(input.running);
+    assert!(input.firing());
+}
+
+#[test]
+fn plain_key_ignores_binding_with_modifier() {
+    let mut simulation = Simulation::new();
+
+    simulation
+        .world_mut()
+        .resource_mut::<Bindings>()
+        .actions
+        .insert(
+            Action::Fire,
+            vec![Binding::key(KeyCode::KeyW).with_modifier(Modifier::Shift)],
+        );
+
+    let entity = simulation.world_mut().spawn(Control).id();
+
+    press(&mut simulation, &[KeyCode::KeyW]);
+
+    let mut input = simulation.world_mut().get_mut::<Input>(entity).unwrap();
+
+    assert_eq!(input.moving, Vec2::NEG_Y);
+    assert!(!input.firing());
+}
+
+#[test]
+fn any_of_several_keys_triggers_action() {
+    let mut simulation = Simulation::new();
+
+    let entity = simulation.world_mut().spawn(Control).id();
+
+    press(&mut simulation, &[KeyCode::ArrowUp]);
+
+    assert_eq!(simulation.get::<Input>(entity).moving, Vec2::NEG_Y);
+
+    simulation
+        .world_mut()
+        .resource_mut::<ButtonInput<KeyCode>>()
+        .release_all();
+
+    press(&mut simulation, &[KeyCode::KeyW]);
+
+    assert_eq!(simulation.get::<Input>(entity).moving, Vec2::NEG_Y);
+}
+
+#[test]
+fn bindings_load_from_file() {
+    let path = write(
+        "bindings_load_from_file",
+        r#"{
+            "actions": {
+                "fire": [
+                    { "button": { "key": "KeyQ" } },
+                    { "button": { "mouse": "Middle" }, "modifiers": ["shift", "control", "shift"] }
+                ]
+            },
+            "mouse_sensitivity": 0.01
+        }"#,
+    );
+
+    let bindings = Bindings::load(&path).unwrap();
+
+    assert_eq!(
+        bindings.actions[&Action::Fire],
+        vec![
+            Binding::key(KeyCode::KeyQ),
+            Binding::mouse(MouseButton::Middle)
+                .with_modifier(Modifier::Control)
+                .with_modifier(Modifier::Shift),
+        ]
+    );
+    assert_eq!(
+        bindings.actions[&Action::MoveForward],
+        Bindings::default().actions[&Action::MoveForward]
+    );
+    assert_eq!(bindings.mouse_sensitivity, 0.01);
+    assert_eq!(
+        bindings.gamepad_sensitivity,
+        Bindings::default().gamepad_sensitivity
+    );
+}
+
+#[test]
+fn conflicting_bindings_file_is_rejected() {
+    let path = write(
+        "conflicting_bindings_file_is_rejected",
+        r#"{
+            "actions": {
+                "grab": [{ "button": { "key": "KeyG" } }],
+                "throw": [{ "button": { "key": "KeyG" } }]
+            }
+        }"#,
+    );
+
+    assert!(matches!(
+        Bindings::load(&path),
+        Err(BindingsError::Conflict {
+            first: Action::Grab,
+            second: Action::Throw,
+            ..
+        })
+    ));
+
+    let bindings = Bindings::load_or_default(&path);
+
+    assert_eq!(
+        bindings.actions[&Action::Grab],
+        Bindings::default().actions[&Action::Grab]
+    );
+}
+
+#[test]
+fn bindings_only_conflict_in_overlapping_contexts() {
+    let mut bindings = Bindings::default();
+
+    bindings
+        .actions
+        .insert(Action::SwimDown, vec![Binding::key(KeyCode::KeyC)]);
+    bindings
+        .actions
+        .insert(Action::Crouch, vec![Binding::key(KeyCode::KeyC)]);
+
+    assert!(bindings.validate().is_ok());
+
+    bindings
+        .actions
+        .insert(Action::Interact, vec![Binding::key(KeyCode::KeyC)]);
+
+    assert!(matches!(
+        bindings.validate(),
+        Err(BindingsError::Conflict { .. })
+    ));
+}
+
+#[test]
+fn malformed_bindings_file_is_a_parse_error() {
+    let path = write(
+        "malformed_bindings_file_is_a_parse_error",
+        "{ \"actions\": [",
+    );
+
+    assert!(matches!(
+        Bindings::load(&path),
+        Err(BindingsError::Parse(_))
+    ));
+}
+
+#[test]
+fn each_source_drives_its_own_entity() {
+    let mut simulation = Simulation::new();
+
+    let first = gamepad(&mut simulation);
+    let second = gamepad(&mut simulation);
+
+    let keyboard = simulation
+        .world_mut()
+        .spawn((Control, InputSource::KeyboardMouse))
+        .id();
+
+    let pad = simulation
+        .world_mut()
+        .spawn((Control, InputSource::Gamepad(first)))
+        .id();
+
+    simulation.world_mut().send_event_batch([
+        RawGamepadEvent::Axis(RawGamepadAxisChangedEvent::new(
+            first,
+            GamepadAxis::LeftStickY,
+            1.0,
+        )),
+        RawGamepadEvent::Button(RawGamepadButtonChangedEvent::new(
+            second,
+            GamepadButton::RightTrigger2,
+            1.0,
+        )),
+    ]);
+
+    press(&mut simulation, &[KeyCode::KeyD]);
+
+    let input = simulation.get::<Input>(keyboard);
+
+    assert_eq!(input.moving, Vec2::X);
+    assert!(!input.running);
+
+    let input = simulation.get::<Input>(pad);
+
+    assert!(
+        input.moving.distance(Vec2::NEG_Y) < 0.001,
+        "moving is {}",
+        input.moving
+    );
+    assert!(!input.running);
+}
+
+#[test]
+fn gamepad_source_ignores_keyboard_modifiers() {
+    let mut simulation = Simulation::new();
+
+    let first = gamepad(&mut simulation);
+
+    simulation
+        .world_mut()
+        .resource_mut::<Bindings>()
+        .actions
+        .insert(
+            Action::Fire,
+            vec![Binding::gamepad(GamepadButton::RightTrigger).with_modifier(Modifier::Shift)],
+        );
+
+    let pad = simulation
+        .world_mut()
+        .spawn((Control, InputSource::Gamepad(first)))
+        .id();
+
+    simulation
+        .world_mut()
+        .send_event(RawGamepadEvent::Button(RawGamepadButtonChangedEvent::new(
+            first,
+            GamepadButton::RightTrigger,
+            1.0,
+        )));
+
+    press(&mut simulation, &[KeyCode::ShiftLeft]);
+
+    assert!(!simulation
+        .world_mut()
+        .get_mut::<Input>(pad)
+        .unwrap()
+        .firing());
+}
+
+#[test]
+fn possessing_moves_the_input_source() {
+    let mut simulation = Simulation::new();
+
+    let first = gamepad(&mut simulation);
+
+    let from = simulation
+        .world_mut()
+        .spawn((Control, InputSource::Gamepad(first)))
+        .id();
+
+    let to = simulation.world_mut().spawn_empty().id();
+
+    camera_controller::possess(simulation.world_mut(), Some(from), to);
+
+    assert!(simulation.world().get::<Control>(to).is_some());
+    assert_eq!(
+        *simulation.get::<InputSource>(to),
+        InputSource::Gamepad(first)
+    );
+    assert!(simulation.world().get::<InputSource>(from).is_none());
+}
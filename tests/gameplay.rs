@@ -0,0 +1,757 @@
+use std::time::Duration;
+
+use bevy::{gltf::GltfExtras, prelude::*};
+use bevy_rapier3d::prelude::{ColliderMassProperties, MassProperties, RigidBody, Velocity};
+use rust_game::{
+    camera_controller::CameraBoom,
+    control::{Control, Input},
+    despawn::Despawn,
+    entities::{
+        block::BlockBundle,
+        player::{Parameters, Status},
+    },
+    explosion::Explosion,
+    headless::Simulation,
+    linker::Linker,
+    replay::{Recording, Replay},
+    water::Water,
+};
+
+#[test]
+fn player_lands_on_ground() {
+    let mut simulation = Simulation::new();
+
+    simulation.ground();
+
+    let player = simulation.player(Vec3::Y * 2.0);
+
+    simulation.step(120);
+
+    assert!(simulation.get::<Status>(player).surface.is_some());
+
+    let height = simulation.get::<Transform>(player).translation.y;
+
+    assert!((height - 1.0).abs() < 0.1, "player rests at {}", height);
+}
+
+#[test]
+fn player_walks_forward() {
+    let mut simulation = Simulation::new();
+
+    simulation.ground();
+
+    let player = simulation.player(Vec3::Y);
+
+    simulation.step(30);
+
+    simulation
+        .world_mut()
+        .get_mut::<Input>(player)
+        .unwrap()
+        .moving = Vec2::NEG_Y;
+
+    simulation.step(64);
+
+    let translation = simulation.get::<Transform>(player).translation;
+
+    assert!(translation.z < -3.0, "player stopped at {}", translation);
+    assert!(
+        translation.x.abs() < 0.01,
+        "player drifted to {}",
+        translation
+    );
+}
+
+fn apex(simulation: &mut Simulation, player: Entity, ticks: usize) -> f32 {
+    let mut height = f32::MIN;
+
+    for _ in 0..ticks {
+        simulation.step(1);
+
+        height = height.max(simulation.get::<Transform>(player).translation.y);
+    }
+
+    height
+}
+
+#[test]
+fn jump_is_buffered_before_landing() {
+    let mut simulation = Simulation::new();
+
+    simulation.ground();
+
+    let player = simulation.player(Vec3::Y * 1.5);
+
+    simulation.step(15);
+
+    assert!(simulation.get::<Status>(player).surface.is_none());
+
+    simulation
+        .world_mut()
+        .get_mut::<Input>(player)
+        .unwrap()
+        .jump();
+
+    simulation.step(10);
+
+    let height = apex(&mut simulation, player, 60);
+
+    assert!(height > 1.5, "player only reached {}", height);
+}
+
+#[test]
+fn jump_is_allowed_shortly_after_leaving_ground() {
+    let mut simulation = Simulation::new();
+
+    let ground = simulation.ground();
+
+    let player = simulation.player(Vec3::Y);
+
+    simulation.step(30);
+
+    assert!(simulation.get::<Status>(player).surface.is_some());
+
+    simulation
+        .world_mut()
+        .entity_mut(ground)
+        .despawn_recursive();
+
+    simulation.step(3);
+
+    assert!(simulation.get::<Status>(player).surface.is_none());
+
+    simulation
+        .world_mut()
+        .get_mut::<Input>(player)
+        .unwrap()
+        .jump();
+
+    simulation.step(2);
+
+    assert!(simulation.get::<Velocity>(player).linvel.y > 0.0);
+}
+
+#[test]
+fn releasing_jump_early_lowers_the_jump() {
+    let jump = |release: Option<usize>| {
+        let mut simulation = Simulation::new();
+
+        simulation.ground();
+
+        let player = simulation.player(Vec3::Y);
+
+        simulation.step(30);
+
+        simulation
+            .world_mut()
+            .get_mut::<Input>(player)
+            .unwrap()
+            .jump();
+
+        if let Some(ticks) = release {
+            simulation.step(ticks);
+
+            simulation
+                .world_mut()
+                .get_mut::<Input>(player)
+                .unwrap()
+                .holding_jump = false;
+        }
+
+        apex(&mut simulation, player, 90)
+    };
+
+    let full = jump(None);
+    let short = jump(Some(3));
+
+    assert!(
+        short < full - 0.2,
+        "released jump reached {}, held jump reached {}",
+        short,
+        full
+    );
+}
+
+fn staircase(simulation: &mut Simulation) {
+    let heights = [0.2, 0.4, 0.6, 0.8, 1.0, 1.0, 1.0, 1.0, 0.8, 0.6, 0.4, 0.2];
+
+    for (index, height) in heights.into_iter().enumerate() {
+        simulation.block(
+            Vec3::new(4.0, height, 0.6),
+            Vec3::new(0.0, height / 2.0, -1.3 - 0.6 * index as f32),
+        );
+    }
+}
+
+#[test]
+fn player_climbs_stairs() {
+    let mut simulation = Simulation::new();
+
+    simulation.ground();
+    staircase(&mut simulation);
+
+    let player = simulation.player(Vec3::Y);
+
+    simulation.step(30);
+
+    simulation
+        .world_mut()
+        .get_mut::<Input>(player)
+        .unwrap()
+        .moving = Vec2::NEG_Y;
+
+    simulation.step(80);
+
+    let translation = simulation.get::<Transform>(player).translation;
+
+    assert!(
+        (translation.z + 4.6).abs() < 1.2,
+        "player stopped at {}",
+        translation
+    );
+    assert!(
+        (translation.y - 2.0).abs() < 0.15,
+        "player is at {} on the landing",
+        translation
+    );
+    assert!(simulation.get::<Status>(player).surface.is_some());
+
+    simulation.step(112);
+
+    let translation = simulation.get::<Transform>(player).translation;
+
+    assert!(translation.z < -10.0, "player stopped at {}", translation);
+    assert!(
+        (translation.y - 1.0).abs() < 0.1,
+        "player is at {} after the stairs",
+        translation
+    );
+    assert!(simulation.get::<Status>(player).surface.is_some());
+}
+
+#[test]
+fn stepping_smooths_head_height() {
+    let mut simulation = Simulation::new();
+
+    simulation.ground();
+    staircase(&mut simulation);
+
+    let player = simulation.player(Vec3::Y);
+
+    simulation.step(30);
+
+    simulation
+        .world_mut()
+        .get_mut::<Input>(player)
+        .unwrap()
+        .moving = Vec2::NEG_Y;
+
+    let head = *simulation.get::<Linker>(player).get("head").unwrap();
+
+    let head_height = |simulation: &Simulation| {
+        simulation.get::<Transform>(player).translation.y
+            + simulation.get::<Transform>(head).translation.y
+    };
+
+    let mut height = head_height(&simulation);
+
+    for _ in 0..192 {
+        simulation.step(1);
+
+        let next = head_height(&simulation);
+
+        assert!(
+            (next - height).abs() < 0.15,
+            "head jumped from {} to {}",
+            height,
+            next
+        );
+
+        height = next;
+    }
+
+    simulation.step(60);
+
+    assert!(simulation.get::<Status>(player).step_offset.abs() < 0.05);
+}
+
+fn platform(simulation: &mut Simulation, velocity: Velocity) -> Entity {
+    let platform = simulation.spawn(&BlockBundle::new(6.0, 0.5, 6.0));
+
+    simulation.world_mut().entity_mut(platform).insert((
+        RigidBody::KinematicVelocityBased,
+        Transform::from_xyz(0.0, 0.25, 0.0),
+        velocity,
+    ));
+
+    platform
+}
+
+#[test]
+fn player_rides_moving_platform() {
+    let mut simulation = Simulation::new();
+
+    let platform = platform(&mut simulation, Velocity::linear(Vec3::X * 2.0));
+
+    let player = simulation.player(Vec3::Y * 1.5);
+
+    simulation.step(30);
+
+    let offset = simulation.get::<Transform>(player).translation
+        - simulation.get::<Transform>(platform).translation;
+
+    simulation.step(64);
+
+    let status = simulation.get::<Status>(player);
+
+    assert_eq!(status.platform, Some(platform));
+
+    let drift = simulation.get::<Transform>(player).translation
+        - simulation.get::<Transform>(platform).translation
+        - offset;
+
+    assert!(drift.length() < 0.1, "player drifted by {}", drift);
+}
+
+#[test]
+fn player_turns_with_rotating_platform() {
+    let mut simulation = Simulation::new();
+
+    let platform = platform(&mut simulation, Velocity::angular(Vec3::Y));
+
+    let player = simulation.player(Vec3::new(1.0, 1.5, 0.0));
+
+    simulation.step(30);
+
+    let relative = |simulation: &Simulation| {
+        let player = simulation.get::<Transform>(player);
+        let platform = simulation.get::<Transform>(platform);
+
+        (
+            platform.rotation.inverse() * (player.translation - platform.translation),
+            platform.rotation.inverse() * player.rotation,
+        )
+    };
+
+    let (offset, rotation) = relative(&simulation);
+
+    simulation.step(64);
+
+    let (new_offset, new_rotation) = relative(&simulation);
+
+    assert!(
+        offset.distance(new_offset) < 0.15,
+        "player moved from {} to {} relative to the platform",
+        offset,
+        new_offset
+    );
+    assert!(rotation.angle_between(new_rotation) < 0.1);
+}
+
+#[test]
+fn rotating_platform_turns_around_its_centre_of_mass() {
+    let mut simulation = Simulation::new();
+
+    let platform = platform(&mut simulation, Velocity::angular(Vec3::Y));
+
+    simulation
+        .world_mut()
+        .entity_mut(platform)
+        .insert(ColliderMassProperties::MassProperties(MassProperties {
+            local_center_of_mass: Vec3::X * 2.0,
+            mass: 200.0,
+            principal_inertia: Vec3::ONE * 100.0,
+            ..default()
+        }));
+
+    let player = simulation.player(Vec3::Y * 1.5);
+
+    simulation.step(30);
+
+    let relative = |simulation: &Simulation| {
+        let player = simulation.get::<Transform>(player);
+        let platform = simulation.get::<Transform>(platform);
+
+        platform.rotation.inverse() * (player.translation - platform.translation)
+    };
+
+    let offset = relative(&simulation);
+
+    simulation.step(64);
+
+    let new_offset = relative(&simulation);
+
+    assert!(
+        offset.distance(new_offset) < 0.15,
+        "player moved from {} to {} relative to the platform",
+        offset,
+        new_offset
+    );
+}
+
+#[test]
+fn jumping_keeps_platform_momentum() {
+    let mut simulation = Simulation::new();
+
+    platform(&mut simulation, Velocity::linear(Vec3::X * 2.0));
+
+    let player = simulation.player(Vec3::Y * 1.5);
+
+    simulation.step(30);
+
+    simulation
+        .world_mut()
+        .get_mut::<Input>(player)
+        .unwrap()
+        .jump();
+
+    simulation.step(1);
+
+    let velocity = simulation.get::<Velocity>(player).linvel;
+
+    assert!(velocity.y > 0.0);
+    assert!(
+        (velocity.x - 2.0).abs() < 0.1,
+        "player jumped with {}",
+        velocity
+    );
+}
+
+fn ramp(simulation: &mut Simulation) -> Vec3 {
+    let rotation = Quat::from_rotation_x(60f32.to_radians());
+
+    let ramp = simulation.spawn(&BlockBundle::new(6.0, 0.5, 20.0));
+
+    simulation
+        .world_mut()
+        .entity_mut(ramp)
+        .insert((RigidBody::Fixed, Transform::from_rotation(rotation)));
+
+    rotation * Vec3::Y
+}
+
+fn slide(simulation: &mut Simulation, normal: Vec3) -> Entity {
+    let player = simulation.player(normal * 0.25 + Vec3::Y * 1.5);
+
+    for _ in 0..60 {
+        simulation.step(1);
+
+        if simulation.get::<Status>(player).slope.is_some() {
+            return player;
+        }
+    }
+
+    panic!("player never reached the slope");
+}
+
+#[test]
+fn player_slides_down_steep_slope() {
+    let mut simulation = Simulation::new();
+
+    let normal = ramp(&mut simulation);
+
+    let player = slide(&mut simulation, normal);
+
+    let start = simulation.get::<Transform>(player).translation;
+
+    simulation.step(20);
+
+    let status = simulation.get::<Status>(player);
+
+    assert!(status.surface.is_none());
+    assert!(status.slope.is_some());
+
+    let translation = simulation.get::<Transform>(player).translation;
+
+    assert!(
+        translation.y < start.y - 0.2,
+        "player stayed at {}",
+        translation
+    );
+    assert!(translation.z > start.z, "player slid to {}", translation);
+}
+
+#[test]
+fn player_cannot_climb_steep_slope() {
+    let mut simulation = Simulation::new();
+
+    let normal = ramp(&mut simulation);
+
+    let player = slide(&mut simulation, normal);
+
+    simulation
+        .world_mut()
+        .get_mut::<Input>(player)
+        .unwrap()
+        .moving = Vec2::NEG_Y;
+
+    let mut height = simulation.get::<Transform>(player).translation.y;
+
+    for _ in 0..20 {
+        simulation.step(1);
+
+        let new_height = simulation.get::<Transform>(player).translation.y;
+
+        assert!(
+            new_height <= height + 0.01,
+            "player climbed to {}",
+            new_height
+        );
+
+        height = new_height;
+    }
+}
+
+#[test]
+fn jumping_off_slope_follows_its_normal() {
+    let mut simulation = Simulation::new();
+
+    let normal = ramp(&mut simulation);
+
+    let player = slide(&mut simulation, normal);
+
+    simulation.step(5);
+
+    simulation
+        .world_mut()
+        .get_mut::<Input>(player)
+        .unwrap()
+        .jump();
+
+    simulation.step(1);
+
+    let velocity = simulation.get::<Velocity>(player).linvel;
+
+    assert!(
+        velocity.dot(normal) > 3.0,
+        "player jumped with {}",
+        velocity
+    );
+}
+
+#[test]
+fn explosion_pushes_bodies_away() {
+    let mut simulation = Simulation::new();
+
+    simulation.ground();
+
+    let block = simulation.spawn(&BlockBundle::default());
+
+    simulation
+        .world_mut()
+        .entity_mut(block)
+        .insert(Transform::from_xyz(2.0, 0.5, 0.0));
+
+    simulation.step(10);
+
+    let explosion = simulation.spawn(&Explosion::new(10.0));
+
+    simulation
+        .world_mut()
+        .entity_mut(explosion)
+        .insert(Transform::default());
+
+    simulation.step(5);
+
+    let velocity = simulation.get::<Velocity>(block).linvel;
+
+    assert!(velocity.x > 0.0, "block moves with {}", velocity);
+}
+
+#[test]
+fn despawn_waits_for_timeout() {
+    let mut simulation = Simulation::new();
+
+    let entity = simulation
+        .world_mut()
+        .spawn(Despawn::after(Duration::from_secs(1)))
+        .id();
+
+    simulation.step(32);
+
+    assert!(simulation.world().get_entity(entity).is_ok());
+
+    simulation.step(40);
+
+    assert!(simulation.world().get_entity(entity).is_err());
+}
+
+#[test]
+fn model_extras_insert_components() {
+    let mut simulation = Simulation::new();
+
+    let entity = simulation
+        .world_mut()
+        .spawn(GltfExtras {
+            value: r#"{"rust_game::water::Water": "{\"buoyancy\": 2.0, \"drag\": 0.5}"}"#
+                .to_owned(),
+        })
+        .with_child(Transform::default())
+        .id();
+
+    simulation.step(1);
+
+    let water = simulation.get::<Water>(entity);
+
+    assert_eq!(water.buoyancy, 2.0);
+    assert_eq!(water.drag, 0.5);
+    assert!(simulation.world().get::<GltfExtras>(entity).is_none());
+}
+
+#[test]
+fn model_extras_spawn_entities() {
+    let mut simulation = Simulation::new();
+
+    let parent = simulation.world_mut().spawn(Transform::default()).id();
+
+    let node = simulation
+        .world_mut()
+        .spawn((
+            GltfExtras {
+                value: r#"{"spawn": "player"}"#.to_owned(),
+            },
+            Transform::from_xyz(1.0, 2.0, 3.0),
+        ))
+        .set_parent(parent)
+        .id();
+
+    simulation.step(1);
+
+    assert!(simulation.world().get_entity(node).is_err());
+
+    let world = simulation.world_mut();
+
+    let mut player_q =
+        world.query_filtered::<(&Transform, &Parent), (With<Status>, With<Control>)>();
+
+    let (transform, player_parent) = player_q.single(world);
+
+    assert!(transform.translation.distance(Vec3::new(1.0, 2.0, 3.0)) < 0.1);
+    assert_eq!(player_parent.get(), parent);
+}
+
+fn replay(recording: &Recording) -> Transform {
+    let mut simulation = Simulation::seeded(7);
+
+    simulation.ground();
+
+    let player = simulation.player(Vec3::Y);
+
+    simulation
+        .world_mut()
+        .entity_mut(player)
+        .insert(Replay::new(recording.clone()));
+
+    simulation.step(recording.ticks.len() + 1);
+
+    *simulation.get::<Transform>(player)
+}
+
+#[test]
+fn replay_is_deterministic() {
+    let mut recording = Recording::new(
+        Transform::from_xyz(0.0, 1.0, 0.0),
+        Velocity::zero(),
+        0.0,
+        Status::default(),
+    );
+
+    for tick in 0..120 {
+        let mut input = Input::default();
+
+        input.moving = Vec2::new(0.0, -1.0);
+        input.running = tick > 60;
+
+        if tick == 40 {
+            input.jump();
+        }
+
+        input.look(Vec2::new(if tick % 10 == 0 { 0.1 } else { 0.0 }, 0.0));
+
+        recording.ticks.push(input);
+    }
+
+    let first = replay(&recording);
+    let second = replay(&recording);
+
+    assert_eq!(first, second);
+    assert!(first.translation.length() > 4.0);
+}
+
+#[test]
+fn replay_restores_pitch_and_status() {
+    let mut simulation = Simulation::new();
+
+    simulation.ground();
+
+    let player = simulation.player(Vec3::Y);
+
+    simulation.step(30);
+
+    let parameters = simulation.get::<Parameters>(player).clone();
+
+    let mut recording = Recording::new(
+        Transform::from_xyz(2.0, 1.0, 0.0),
+        Velocity::zero(),
+        0.4,
+        Status {
+            current_collider_height: parameters.crouching_half_height(),
+            ..default()
+        },
+    );
+
+    let mut input = Input::default();
+
+    input.crouching = true;
+
+    recording.ticks.push(input);
+
+    simulation
+        .world_mut()
+        .entity_mut(player)
+        .insert(Replay::new(recording));
+
+    simulation.step(1);
+
+    assert_eq!(simulation.get::<CameraBoom>(player).pitch, 0.4);
+    assert_eq!(
+        simulation.get::<Status>(player).current_collider_height,
+        parameters.crouching_half_height()
+    );
+    assert!((simulation.get::<Transform>(player).translation.x - 2.0).abs() < 0.1);
+}
+
+#[test]
+fn replay_restores_velocity() {
+    let mut simulation = Simulation::new();
+
+    simulation.ground();
+
+    let player = simulation.player(Vec3::Y);
+
+    simulation.step(30);
+
+    let mut recording = Recording::new(
+        Transform::from_xyz(0.0, 1.0, 0.0),
+        Velocity::linear(Vec3::X * 5.0),
+        0.0,
+        Status::default(),
+    );
+
+    recording.ticks.push(Input::default());
+
+    simulation
+        .world_mut()
+        .entity_mut(player)
+        .insert(Replay::new(recording));
+
+    simulation.step(1);
+
+    let velocity = simulation.get::<Velocity>(player).linvel;
+
+    assert!(
+        velocity.x > 4.0,
+        "player replayed with velocity {}",
+        velocity
+    );
+}
@@ -0,0 +1,84 @@
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::{Collider, RigidBody};
+use rust_game::{
+    entities::player::Status,
+    gravity::{Gravity, GravityAttractor, GravityZone},
+    headless::Simulation,
+};
+
+#[test]
+fn player_stands_on_wall_in_gravity_zone() {
+    let mut simulation = Simulation::new();
+
+    simulation.world_mut().spawn((
+        GravityZone {
+            vector: Vec3::NEG_X * 9.81,
+        },
+        Collider::cuboid(20.0, 20.0, 20.0),
+        Transform::default(),
+    ));
+
+    simulation.block(Vec3::new(1.0, 40.0, 40.0), Vec3::new(-0.5, 0.0, 0.0));
+
+    let player = simulation.player(Vec3::X * 1.5);
+
+    simulation.step(180);
+
+    assert!(simulation
+        .get::<Gravity>(player)
+        .vector
+        .abs_diff_eq(Vec3::NEG_X * 9.81, 0.001));
+
+    let normal = simulation.get::<Status>(player).surface.unwrap();
+
+    assert!(normal.dot(Vec3::X) > 0.99, "player stands on {}", normal);
+
+    let transform = simulation.get::<Transform>(player);
+
+    assert!(
+        transform.up().dot(Vec3::X) > 0.95,
+        "player is oriented towards {:?}",
+        transform.up()
+    );
+    assert!(
+        (transform.translation.x - 1.0).abs() < 0.1,
+        "player rests at {}",
+        transform.translation
+    );
+}
+
+#[test]
+fn player_walks_around_planet() {
+    let mut simulation = Simulation::new();
+
+    simulation
+        .world_mut()
+        .spawn((RigidBody::Fixed, Collider::ball(5.0), Transform::default()));
+
+    simulation.world_mut().spawn((
+        GravityAttractor::default(),
+        Collider::ball(20.0),
+        Transform::default(),
+    ));
+
+    let player = simulation.player(Vec3::Z * 7.0);
+
+    simulation.step(180);
+
+    assert!(simulation.get::<Status>(player).surface.is_some());
+
+    let transform = simulation.get::<Transform>(player);
+
+    let radial = transform.translation.normalize();
+
+    assert!(
+        (transform.translation.length() - 6.0).abs() < 0.1,
+        "player rests at {}",
+        transform.translation
+    );
+    assert!(
+        transform.up().dot(radial) > 0.95,
+        "player is oriented towards {:?}",
+        transform.up()
+    );
+}
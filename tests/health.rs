@@ -0,0 +1,178 @@
+use bevy::prelude::*;
+use rust_game::{
+    entities::{block::BlockBundle, player::Status},
+    explosion::Explosion,
+    headless::Simulation,
+    health::{Damage, DamageKind, Health},
+};
+
+fn prop(simulation: &mut Simulation, position: Vec3, health: f32) -> Entity {
+    let block = simulation.spawn(&BlockBundle::default());
+
+    simulation
+        .world_mut()
+        .entity_mut(block)
+        .insert((Health::new(health), Transform::from_translation(position)));
+
+    block
+}
+
+#[test]
+fn explosion_damage_falls_off_with_distance() {
+    let mut simulation = Simulation::new();
+
+    let near = prop(&mut simulation, Vec3::X * 2.0, 1000.0);
+    let far = prop(&mut simulation, Vec3::NEG_X * 6.0, 1000.0);
+
+    simulation.step(1);
+
+    let explosion = simulation.spawn(&Explosion::new(10.0));
+
+    simulation
+        .world_mut()
+        .entity_mut(explosion)
+        .insert(Transform::default());
+
+    simulation.step(5);
+
+    let near = simulation.get::<Health>(near).current;
+    let far = simulation.get::<Health>(far).current;
+
+    assert!(far < 1000.0, "far prop has {} health", far);
+    assert!(near < far, "near prop has {}, far prop has {}", near, far);
+}
+
+#[test]
+fn destroyed_prop_is_despawned() {
+    let mut simulation = Simulation::new();
+
+    let block = prop(&mut simulation, Vec3::X * 2.0, 10.0);
+
+    simulation.step(1);
+
+    let explosion = simulation.spawn(&Explosion::new(10.0));
+
+    simulation
+        .world_mut()
+        .entity_mut(explosion)
+        .insert(Transform::default());
+
+    simulation.step(5);
+
+    assert!(simulation.world().get_entity(block).is_err());
+}
+
+#[test]
+fn long_fall_damages_player() {
+    let mut simulation = Simulation::new();
+
+    simulation.ground();
+
+    let player = simulation.player(Vec3::Y * 20.0);
+
+    simulation.step(180);
+
+    assert!(simulation.get::<Status>(player).surface.is_some());
+
+    let health = simulation.get::<Health>(player);
+
+    assert!(
+        health.current < health.max,
+        "player has {} health",
+        health.current
+    );
+}
+
+#[test]
+fn short_fall_is_harmless() {
+    let mut simulation = Simulation::new();
+
+    simulation.ground();
+
+    let player = simulation.player(Vec3::Y * 3.0);
+
+    simulation.step(120);
+
+    assert!(simulation.get::<Status>(player).surface.is_some());
+
+    let health = simulation.get::<Health>(player);
+
+    assert_eq!(health.current, health.max);
+}
+
+#[test]
+fn player_respawns_after_death() {
+    let mut simulation = Simulation::new();
+
+    simulation.ground();
+
+    let player = simulation.player(Vec3::Y * 1.0);
+
+    simulation.step(30);
+
+    simulation
+        .world_mut()
+        .get_mut::<Transform>(player)
+        .unwrap()
+        .translation = Vec3::new(5.0, 1.0, 5.0);
+
+    simulation.world_mut().send_event(Damage {
+        target: player,
+        source: None,
+        amount: 500.0,
+        kind: DamageKind::Generic,
+    });
+
+    simulation.step(30);
+
+    let health = simulation.get::<Health>(player);
+
+    assert_eq!(health.current, health.max);
+
+    let translation = simulation.get::<Transform>(player).translation;
+
+    assert!(
+        translation.distance(Vec3::Y) < 0.1,
+        "player respawned at {}",
+        translation
+    );
+}
+
+#[test]
+fn respawning_mid_fall_does_not_deal_fall_damage() {
+    let mut simulation = Simulation::new();
+
+    simulation.ground();
+
+    let player = simulation.player(Vec3::Y * 1.0);
+
+    simulation.step(30);
+
+    simulation
+        .world_mut()
+        .get_mut::<Transform>(player)
+        .unwrap()
+        .translation = Vec3::Y * 60.0;
+
+    simulation.step(120);
+
+    assert!(simulation.get::<Status>(player).falling_speed > 10.0);
+
+    simulation.world_mut().send_event(Damage {
+        target: player,
+        source: None,
+        amount: 500.0,
+        kind: DamageKind::Generic,
+    });
+
+    simulation.step(30);
+
+    let status = simulation.get::<Status>(player);
+
+    assert!(status.surface.is_some());
+    assert_eq!(status.falling_speed, 0.0);
+
+    let health = simulation.get::<Health>(player);
+
+    assert_eq!(health.current, health.max);
+}
@@ -0,0 +1,83 @@
+use bevy::prelude::*;
+use rust_game::{
+    control::Input,
+    headless::Simulation,
+    interaction::{Focus, FocusChanged, Interactable, Interacted},
+};
+
+#[test]
+fn player_focuses_and_interacts_with_target() {
+    let mut simulation = Simulation::new();
+
+    simulation.listen::<FocusChanged>();
+    simulation.listen::<Interacted>();
+
+    simulation.ground();
+
+    let door = simulation.block(Vec3::new(1.0, 4.0, 0.5), Vec3::new(0.0, 2.0, -1.5));
+
+    simulation
+        .world_mut()
+        .entity_mut(door)
+        .insert(Interactable::new("open"));
+
+    let player = simulation.player(Vec3::Y);
+
+    simulation.step(30);
+
+    let changes = simulation.received::<FocusChanged>();
+
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].by, player);
+    assert_eq!(changes[0].previous, None);
+    assert_eq!(changes[0].current, Some(door));
+    assert_eq!(simulation.get::<Focus>(player).target, Some(door));
+
+    assert!(simulation.received::<Interacted>().is_empty());
+
+    simulation
+        .world_mut()
+        .get_mut::<Input>(player)
+        .unwrap()
+        .interact();
+
+    simulation.step(1);
+
+    let interactions = simulation.received::<Interacted>();
+
+    assert_eq!(interactions.len(), 1);
+    assert_eq!(interactions[0].by, player);
+    assert_eq!(interactions[0].target, door);
+
+    simulation
+        .world_mut()
+        .entity_mut(door)
+        .insert(Transform::from_xyz(0.0, 2.0, -10.0));
+
+    simulation.step(5);
+
+    let changes = simulation.received::<FocusChanged>();
+
+    assert_eq!(changes.len(), 2);
+    assert_eq!(changes[1].previous, Some(door));
+    assert_eq!(changes[1].current, None);
+    assert_eq!(simulation.get::<Focus>(player).target, None);
+}
+
+#[test]
+fn plain_blocks_are_not_focused() {
+    let mut simulation = Simulation::new();
+
+    simulation.listen::<FocusChanged>();
+
+    simulation.ground();
+
+    simulation.block(Vec3::new(1.0, 4.0, 0.5), Vec3::new(0.0, 2.0, -1.5));
+
+    let player = simulation.player(Vec3::Y);
+
+    simulation.step(30);
+
+    assert!(simulation.received::<FocusChanged>().is_empty());
+    assert_eq!(simulation.get::<Focus>(player).target, None);
+}
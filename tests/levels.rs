@@ -0,0 +1,92 @@
+use bevy::prelude::*;
+use rust_game::{
+    entities::{block::BlockBundle, fireball::Fireball},
+    headless::Simulation,
+    levels::{CurrentLevel, Level, LevelState, Levels, SwitchLevel},
+    library::Spawnable,
+};
+
+struct Arena;
+
+impl Spawnable for Arena {
+    fn spawn<'a>(&self, commands: &'a mut Commands) -> EntityCommands<'a> {
+        let entity = commands
+            .spawn((Name::new("arena"), Transform::default()))
+            .id();
+
+        BlockBundle::default().spawn(commands).set_parent(entity);
+
+        commands.entity(entity)
+    }
+}
+
+fn levels(simulation: &mut Simulation) -> Vec<Entity> {
+    let world = simulation.world_mut();
+
+    world
+        .query_filtered::<Entity, With<Level>>()
+        .iter(world)
+        .collect()
+}
+
+#[test]
+fn switching_levels_replaces_previous_level() {
+    let mut simulation = Simulation::new();
+
+    simulation
+        .world_mut()
+        .resource_mut::<Levels>()
+        .register("arena", Arena);
+
+    simulation.world_mut().send_event(SwitchLevel::new("arena"));
+
+    simulation.step(4);
+
+    assert_eq!(
+        *simulation.world().resource::<State<LevelState>>().get(),
+        LevelState::Running
+    );
+    assert_eq!(
+        simulation
+            .world()
+            .resource::<CurrentLevel>()
+            .name
+            .as_deref(),
+        Some("arena")
+    );
+
+    let previous = levels(&mut simulation);
+
+    let fireball = simulation.spawn(&Fireball);
+
+    simulation.world_mut().send_event(SwitchLevel::new("arena"));
+
+    simulation.step(6);
+
+    assert!(simulation.world().get_entity(fireball).is_err());
+    assert!(previous
+        .iter()
+        .all(|entity| simulation.world().get_entity(*entity).is_err()));
+    assert_eq!(levels(&mut simulation).len(), 1);
+    assert_eq!(
+        *simulation.world().resource::<State<LevelState>>().get(),
+        LevelState::Running
+    );
+}
+
+#[test]
+fn unknown_levels_are_ignored() {
+    let mut simulation = Simulation::new();
+
+    simulation
+        .world_mut()
+        .send_event(SwitchLevel::new("missing"));
+
+    simulation.step(2);
+
+    assert_eq!(
+        *simulation.world().resource::<State<LevelState>>().get(),
+        LevelState::Unloaded
+    );
+    assert!(levels(&mut simulation).is_empty());
+}
@@ -0,0 +1,132 @@
+use bevy::prelude::*;
+use rust_game::{
+    control::Input,
+    entities::player::{
+        BlockedFromStanding, CrouchEnded, CrouchStarted, Jumped, Landed, LeftGround,
+    },
+    headless::Simulation,
+};
+
+#[test]
+fn landing_reports_impact_speed_and_surface() {
+    let mut simulation = Simulation::new();
+
+    simulation.listen::<Landed>();
+
+    let ground = simulation.ground();
+
+    let player = simulation.player(Vec3::Y * 6.0);
+
+    simulation.step(120);
+
+    let landings = simulation.received::<Landed>();
+
+    assert_eq!(landings.len(), 1);
+    assert_eq!(landings[0].entity, player);
+    assert_eq!(landings[0].surface, ground);
+    assert!(
+        landings[0].impact_speed > 5.0,
+        "player landed at {}",
+        landings[0].impact_speed
+    );
+}
+
+#[test]
+fn jumping_leaves_ground_and_lands_again() {
+    let mut simulation = Simulation::new();
+
+    simulation.ground();
+
+    let player = simulation.player(Vec3::Y);
+
+    simulation.step(60);
+
+    simulation.listen::<Jumped>();
+    simulation.listen::<LeftGround>();
+    simulation.listen::<Landed>();
+
+    simulation
+        .world_mut()
+        .get_mut::<Input>(player)
+        .unwrap()
+        .jump();
+
+    simulation.step(120);
+
+    assert_eq!(simulation.received::<Jumped>().len(), 1);
+    assert_eq!(simulation.received::<LeftGround>().len(), 1);
+    assert_eq!(simulation.received::<Landed>().len(), 1);
+}
+
+#[test]
+fn crouching_starts_and_ends() {
+    let mut simulation = Simulation::new();
+
+    simulation.ground();
+
+    let player = simulation.player(Vec3::Y);
+
+    simulation.step(60);
+
+    simulation.listen::<CrouchStarted>();
+    simulation.listen::<CrouchEnded>();
+
+    simulation
+        .world_mut()
+        .get_mut::<Input>(player)
+        .unwrap()
+        .crouching = true;
+
+    simulation.step(30);
+
+    assert_eq!(simulation.received::<CrouchStarted>().len(), 1);
+    assert!(simulation.received::<CrouchEnded>().is_empty());
+
+    simulation
+        .world_mut()
+        .get_mut::<Input>(player)
+        .unwrap()
+        .crouching = false;
+
+    simulation.step(60);
+
+    assert_eq!(simulation.received::<CrouchStarted>().len(), 1);
+    assert_eq!(simulation.received::<CrouchEnded>().len(), 1);
+}
+
+#[test]
+fn low_ceiling_blocks_standing_up() {
+    let mut simulation = Simulation::new();
+
+    simulation.ground();
+
+    let player = simulation.player(Vec3::Y);
+
+    simulation.step(60);
+
+    simulation
+        .world_mut()
+        .get_mut::<Input>(player)
+        .unwrap()
+        .crouching = true;
+
+    simulation.step(30);
+
+    simulation.block(Vec3::new(4.0, 1.0, 4.0), Vec3::new(0.0, 2.0, 0.0));
+
+    simulation.listen::<BlockedFromStanding>();
+    simulation.listen::<CrouchEnded>();
+
+    simulation.step(5);
+
+    simulation
+        .world_mut()
+        .get_mut::<Input>(player)
+        .unwrap()
+        .crouching = false;
+
+    simulation.step(30);
+
+    assert_eq!(simulation.received::<BlockedFromStanding>().len(), 1);
+    assert!(simulation.received::<CrouchEnded>().is_empty());
+}
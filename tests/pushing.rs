@@ -0,0 +1,90 @@
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::Velocity;
+use rust_game::{
+    control::Input,
+    entities::{block::BlockBundle, player::Parameters},
+    headless::Simulation,
+};
+
+fn push(mass: f32, running: bool) -> (Vec3, Vec3, f32) {
+    let mut simulation = Simulation::new();
+
+    simulation.ground();
+
+    let block = simulation.spawn(&BlockBundle::new(1.0, 1.0, 1.0).with_mass(mass));
+
+    simulation
+        .world_mut()
+        .entity_mut(block)
+        .insert(Transform::from_xyz(0.0, 0.5, -1.5));
+
+    let player = simulation.player(Vec3::Y);
+
+    simulation.step(30);
+
+    simulation
+        .world_mut()
+        .get_mut::<Input>(player)
+        .unwrap()
+        .moving = Vec2::NEG_Y;
+
+    simulation
+        .world_mut()
+        .get_mut::<Input>(player)
+        .unwrap()
+        .running = running;
+
+    simulation.step(120);
+
+    (
+        simulation.get::<Velocity>(player).linvel,
+        simulation.get::<Velocity>(block).linvel,
+        simulation.get::<Transform>(block).translation.z,
+    )
+}
+
+#[test]
+fn light_block_is_pushed_near_walking_speed() {
+    let walking_speed = Parameters::default().walking_speed;
+
+    let (player, block, position) = push(12.5, false);
+
+    assert!(-block.z > walking_speed * 0.6, "block moves at {}", block);
+    assert!(
+        (player.z - block.z).abs() < 0.5,
+        "player moves at {}, block at {}",
+        player,
+        block
+    );
+    assert!(position < -4.0, "block is at {}", position);
+}
+
+#[test]
+fn heavy_block_slows_the_player_down() {
+    let walking_speed = Parameters::default().walking_speed;
+
+    let (player, block, position) = push(200.0, false);
+
+    assert!(
+        -player.z < walking_speed * 0.5,
+        "player moves at {}",
+        player
+    );
+    assert!(-block.z < walking_speed * 0.5, "block moves at {}", block);
+    assert!(position < -1.7, "block is at {}", position);
+}
+
+#[test]
+fn running_pushes_light_block_faster() {
+    let walking_speed = Parameters::default().walking_speed;
+
+    let (player, block, _) = push(12.5, true);
+
+    assert!(-block.z > walking_speed * 1.2, "block moves at {}", block);
+    assert!(
+        (player.z - block.z).abs() < 0.5,
+        "player moves at {}, block at {}",
+        player,
+        block
+    );
+}
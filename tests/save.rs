@@ -0,0 +1,279 @@
+use std::{env, f32::consts, time::Duration};
+
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::{
+    Collider, ColliderMassProperties, ReadMassProperties, RigidBody, SolverGroups, Velocity,
+};
+use rust_game::{
+    camera_controller::CameraController,
+    control::Input,
+    despawn::Despawn,
+    entities::{
+        block::{Block, BlockBundle},
+        fireball::Fireball,
+        player::{Carrier, Held, Parameters, PlayerCamera},
+        traffic_cone::TrafficCone,
+    },
+    headless::Simulation,
+    linker::Linker,
+    save,
+};
+
+fn players(simulation: &mut Simulation) -> Vec<Entity> {
+    let world = simulation.world_mut();
+
+    world
+        .query_filtered::<Entity, With<Parameters>>()
+        .iter(world)
+        .collect()
+}
+
+#[test]
+fn quickload_restores_player() {
+    let mut simulation = Simulation::new();
+
+    simulation.ground();
+
+    let player = simulation.player(Vec3::Y);
+
+    simulation.step(60);
+
+    let saved = *simulation.get::<Transform>(player);
+
+    let path = env::temp_dir().join("rust_game_quickload_restores_player.ron");
+
+    save::save(simulation.world_mut(), &path).unwrap();
+
+    simulation
+        .world_mut()
+        .entity_mut(player)
+        .insert(Velocity::linear(Vec3::X * 5.0));
+
+    simulation.step(60);
+
+    save::load(simulation.world_mut(), &path).unwrap();
+
+    simulation.step(1);
+
+    let restored = players(&mut simulation);
+
+    assert_eq!(restored.len(), 1);
+    assert!(simulation.world().get_entity(player).is_err());
+
+    let player = restored[0];
+
+    let transform = simulation.get::<Transform>(player);
+
+    assert!(
+        transform.translation.distance(saved.translation) < 0.1,
+        "player restored at {}, saved at {}",
+        transform.translation,
+        saved.translation
+    );
+
+    let head = *simulation.get::<Linker>(player).get("head").unwrap();
+
+    assert_eq!(simulation.get::<Parent>(head).get(), player);
+
+    let camera = simulation.get::<CameraController>(player).target;
+
+    assert!(simulation.world().get::<PlayerCamera>(camera).is_some());
+    assert_eq!(simulation.get::<Parent>(camera).get(), head);
+}
+
+#[test]
+fn quickload_resumes_despawn_timers() {
+    let mut simulation = Simulation::new();
+
+    let fireball = simulation.spawn(&Fireball);
+
+    simulation
+        .world_mut()
+        .entity_mut(fireball)
+        .insert(Despawn::after(Duration::from_secs(1)));
+
+    simulation.step(32);
+
+    let path = env::temp_dir().join("rust_game_quickload_resumes_despawn_timers.ron");
+
+    save::save(simulation.world_mut(), &path).unwrap();
+
+    simulation.step(40);
+
+    assert!(simulation.world().get_entity(fireball).is_err());
+
+    save::load(simulation.world_mut(), &path).unwrap();
+
+    let world = simulation.world_mut();
+
+    let fireball = world
+        .query_filtered::<Entity, With<Fireball>>()
+        .single(world);
+
+    simulation.step(20);
+
+    assert!(simulation.world().get_entity(fireball).is_ok());
+
+    simulation.step(20);
+
+    assert!(simulation.world().get_entity(fireball).is_err());
+}
+
+#[test]
+fn quickload_restores_props() {
+    let mut simulation = Simulation::new();
+
+    simulation.ground();
+
+    let block = simulation.spawn(&BlockBundle::new(0.5, 0.5, 0.5));
+
+    simulation
+        .world_mut()
+        .entity_mut(block)
+        .insert(Transform::from_xyz(2.0, 0.25, 0.0));
+
+    simulation.step(30);
+
+    let saved = simulation.get::<Transform>(block).translation;
+
+    let path = env::temp_dir().join("rust_game_quickload_restores_props.ron");
+
+    save::save(simulation.world_mut(), &path).unwrap();
+
+    simulation
+        .world_mut()
+        .entity_mut(block)
+        .insert(Velocity::linear(Vec3::X * 5.0));
+
+    simulation.step(30);
+
+    save::load(simulation.world_mut(), &path).unwrap();
+
+    simulation.step(30);
+
+    assert!(simulation.world().get_entity(block).is_err());
+
+    let world = simulation.world_mut();
+
+    let blocks = world
+        .query::<(Entity, &Block)>()
+        .iter(world)
+        .filter(|(_, block)| block.size == Vec3::splat(0.5))
+        .map(|(entity, _)| entity)
+        .collect::<Vec<_>>();
+
+    assert_eq!(blocks.len(), 1);
+
+    let block = blocks[0];
+
+    assert!(simulation.world().get::<Collider>(block).is_some());
+
+    let translation = simulation.get::<Transform>(block).translation;
+
+    assert!(
+        translation.distance(saved) < 0.1,
+        "block restored at {}, saved at {}",
+        translation,
+        saved
+    );
+}
+
+#[test]
+fn quickload_releases_held_bodies() {
+    let mut simulation = Simulation::new();
+
+    simulation.ground();
+
+    let body = simulation
+        .world_mut()
+        .spawn((
+            Transform::from_xyz(0.0, 0.25, -1.2),
+            RigidBody::Dynamic,
+            Collider::cuboid(0.25, 0.25, 0.25),
+            ColliderMassProperties::Mass(3.0),
+            ReadMassProperties::default(),
+            Velocity::default(),
+        ))
+        .id();
+
+    let player = simulation.player(Vec3::Y);
+
+    simulation.step(30);
+
+    for _ in 0..10 {
+        simulation
+            .world_mut()
+            .get_mut::<Input>(player)
+            .unwrap()
+            .look(Vec2::new(0.0, -consts::FRAC_PI_4 / 10.0));
+
+        simulation.step(1);
+    }
+
+    simulation
+        .world_mut()
+        .get_mut::<Input>(player)
+        .unwrap()
+        .grab();
+
+    simulation.step(1);
+
+    assert_eq!(simulation.get::<Carrier>(player).held, Some(body));
+    assert!(simulation.world().get::<SolverGroups>(body).is_some());
+
+    let path = env::temp_dir().join("rust_game_quickload_releases_held_bodies.ron");
+
+    save::save(simulation.world_mut(), &path).unwrap();
+
+    save::load(simulation.world_mut(), &path).unwrap();
+
+    simulation.step(1);
+
+    assert!(simulation.world().get::<Held>(body).is_none());
+    assert!(simulation.world().get::<SolverGroups>(body).is_none());
+
+    let player = players(&mut simulation)[0];
+
+    assert_eq!(simulation.get::<Carrier>(player).held, None);
+}
+
+fn settle(simulation: &mut Simulation, entity: Entity) {
+    for _ in 0..1000 {
+        if simulation.get::<ReadMassProperties>(entity).mass > 0.0 {
+            return;
+        }
+
+        simulation.step(1);
+    }
+
+    panic!("{} never got a collider", entity);
+}
+
+#[test]
+fn quickload_rebuilds_models() {
+    let mut simulation = Simulation::new();
+
+    simulation.ground();
+
+    let cone = simulation.spawn(&TrafficCone);
+
+    settle(&mut simulation, cone);
+
+    let path = env::temp_dir().join("rust_game_quickload_rebuilds_models.ron");
+
+    save::save(simulation.world_mut(), &path).unwrap();
+
+    save::load(simulation.world_mut(), &path).unwrap();
+
+    let world = simulation.world_mut();
+
+    let cone = world
+        .query_filtered::<Entity, With<TrafficCone>>()
+        .single(world);
+
+    assert!(simulation.world().get::<Children>(cone).is_none());
+
+    settle(&mut simulation, cone);
+
+    assert!(simulation.world().get::<SceneRoot>(cone).is_some());
+}
@@ -0,0 +1,243 @@
+use bevy::prelude::*;
+use rust_game::{
+    camera_controller::{self, CameraController, Spectate},
+    control::{Control, Input},
+    entities::{
+        player::{LocalPlayer, Player},
+        spectator::{self, FreeFly},
+    },
+    headless::Simulation,
+};
+
+fn is_viewed(simulation: &Simulation, entity: Entity) -> bool {
+    let camera = simulation.get::<CameraController>(entity).target;
+
+    simulation.get::<Camera>(camera).is_active
+}
+
+#[test]
+fn multiple_spectators_pick_the_first_entity() {
+    let mut simulation = Simulation::new();
+
+    let first = simulation.spawn(&LocalPlayer);
+    let second = simulation.spawn(&LocalPlayer);
+
+    simulation.step(1);
+
+    assert!(is_viewed(&simulation, first.min(second)));
+    assert!(!is_viewed(&simulation, first.max(second)));
+}
+
+#[test]
+fn cycling_moves_spectate_but_keeps_control() {
+    let mut simulation = Simulation::new();
+
+    simulation.ground();
+
+    let local = simulation.spawn(&LocalPlayer);
+    let other = simulation.spawn(&Player);
+
+    simulation.step(1);
+
+    camera_controller::cycle(simulation.world_mut());
+
+    simulation.step(1);
+
+    let world = simulation.world();
+
+    assert!(world.get::<Spectate>(other).is_some());
+    assert!(world.get::<Control>(other).is_none());
+    assert!(world.get::<Spectate>(local).is_none());
+    assert!(world.get::<Control>(local).is_some());
+
+    assert!(is_viewed(&simulation, other));
+    assert!(!is_viewed(&simulation, local));
+
+    camera_controller::cycle(simulation.world_mut());
+
+    simulation.step(1);
+
+    assert!(simulation.world().get::<Spectate>(local).is_some());
+    assert!(is_viewed(&simulation, local));
+}
+
+#[test]
+fn free_fly_detaches_from_player_and_returns() {
+    let mut simulation = Simulation::new();
+
+    simulation.ground();
+
+    let player = simulation.spawn(&LocalPlayer);
+
+    simulation
+        .world_mut()
+        .entity_mut(player)
+        .insert(Transform::from_xyz(0.0, 1.0, 0.0));
+
+    simulation.step(30);
+
+    spectator::toggle(simulation.world_mut());
+
+    let world = simulation.world_mut();
+
+    let spectator = world
+        .query_filtered::<Entity, With<FreeFly>>()
+        .single(world);
+
+    assert!(world.get::<Spectate>(spectator).is_some());
+    assert!(world.get::<Control>(spectator).is_some());
+    assert!(world.get::<Control>(player).is_none());
+
+    let start = *simulation.get::<Transform>(spectator);
+
+    simulation
+        .world_mut()
+        .entity_mut(spectator)
+        .remove::<Control>();
+
+    let mut input = simulation.world_mut().get_mut::<Input>(spectator).unwrap();
+
+    input.moving = Vec2::NEG_Y;
+    input.running = true;
+
+    simulation.step(16);
+
+    let moved = simulation.get::<Transform>(spectator).translation;
+
+    let distance = (moved - start.translation).dot(start.forward().into());
+
+    assert!(distance > 5.0, "spectator moved by {}", distance);
+    assert!(is_viewed(&simulation, spectator));
+    assert!(!is_viewed(&simulation, player));
+
+    spectator::toggle(simulation.world_mut());
+
+    simulation.step(1);
+
+    assert!(simulation.world().get_entity(spectator).is_err());
+    assert!(simulation.world().get::<Spectate>(player).is_some());
+    assert!(is_viewed(&simulation, player));
+}
+
+#[test]
+fn cycling_clears_every_spectator() {
+    let mut simulation = Simulation::new();
+
+    simulation.ground();
+
+    let first = simulation.spawn(&LocalPlayer);
+    let second = simulation.spawn(&LocalPlayer);
+    let third = simulation.spawn(&Player);
+
+    simulation.step(1);
+
+    camera_controller::cycle(simulation.world_mut());
+
+    simulation.step(1);
+
+    let world = simulation.world_mut();
+
+    let spectators = world
+        .query_filtered::<Entity, With<Spectate>>()
+        .iter(world)
+        .collect::<Vec<_>>();
+
+    assert_eq!(spectators, vec![second]);
+    assert!(is_viewed(&simulation, second));
+    assert!(!is_viewed(&simulation, first));
+    assert!(!is_viewed(&simulation, third));
+
+    camera_controller::cycle(simulation.world_mut());
+
+    simulation.step(1);
+
+    assert!(simulation.world().get::<Spectate>(third).is_some());
+    assert!(is_viewed(&simulation, third));
+}
+
+#[test]
+fn removing_spectate_activates_remaining_spectator() {
+    let mut simulation = Simulation::new();
+
+    let first = simulation.spawn(&LocalPlayer);
+    let second = simulation.spawn(&LocalPlayer);
+
+    simulation.step(1);
+
+    let (viewed, other) = (first.min(second), first.max(second));
+
+    simulation
+        .world_mut()
+        .entity_mut(viewed)
+        .remove::<Spectate>();
+
+    simulation.step(1);
+
+    assert!(is_viewed(&simulation, other));
+    assert!(!is_viewed(&simulation, viewed));
+}
+
+#[test]
+fn free_fly_keeps_uncontrolled_player_uncontrolled() {
+    let mut simulation = Simulation::new();
+
+    simulation.ground();
+
+    let player = simulation.spawn(&Player);
+
+    simulation.world_mut().entity_mut(player).insert(Spectate);
+
+    simulation.step(1);
+
+    spectator::toggle(simulation.world_mut());
+
+    simulation.step(1);
+
+    spectator::toggle(simulation.world_mut());
+
+    simulation.step(1);
+
+    assert!(simulation.world().get::<Spectate>(player).is_some());
+    assert!(simulation.world().get::<Control>(player).is_none());
+}
+
+#[test]
+fn free_fly_stops_the_player_it_leaves() {
+    let mut simulation = Simulation::new();
+
+    simulation.ground();
+
+    let player = simulation.spawn(&LocalPlayer);
+
+    simulation
+        .world_mut()
+        .entity_mut(player)
+        .insert(Transform::from_xyz(0.0, 1.0, 0.0));
+
+    simulation.step(30);
+
+    simulation
+        .world_mut()
+        .resource_mut::<ButtonInput<KeyCode>>()
+        .press(KeyCode::KeyW);
+
+    simulation.step(2);
+
+    assert_eq!(simulation.get::<Input>(player).moving, Vec2::NEG_Y);
+
+    spectator::toggle(simulation.world_mut());
+
+    simulation.step(16);
+
+    let start = simulation.get::<Transform>(player).translation;
+
+    simulation.step(64);
+
+    let moved = simulation
+        .get::<Transform>(player)
+        .translation
+        .distance(start);
+
+    assert_eq!(simulation.get::<Input>(player).moving, Vec2::ZERO);
+    assert!(moved < 0.1, "player kept walking for {}", moved);
+}
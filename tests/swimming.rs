@@ -0,0 +1,136 @@
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::{Collider, GravityScale, Velocity};
+use rust_game::{
+    control::Input,
+    entities::player::{Parameters, Status},
+    headless::Simulation,
+    water::Water,
+};
+
+fn pool(simulation: &mut Simulation, buoyancy: f32) -> Entity {
+    simulation
+        .world_mut()
+        .spawn((
+            Water {
+                buoyancy,
+                ..default()
+            },
+            Collider::cuboid(20.0, 20.0, 20.0),
+            Transform::from_xyz(0.0, -20.0, 0.0),
+        ))
+        .id()
+}
+
+fn swimmer(simulation: &mut Simulation, buoyancy: f32) -> (Entity, Entity) {
+    let water = pool(simulation, buoyancy);
+
+    let player = simulation.player(Vec3::Y * -10.0);
+
+    simulation.step(10);
+
+    (player, water)
+}
+
+#[test]
+fn player_enters_water() {
+    let mut simulation = Simulation::new();
+
+    let water = pool(&mut simulation, 1.0);
+
+    let player = simulation.player(Vec3::Y * 5.0);
+
+    simulation.step(10);
+
+    assert_eq!(simulation.get::<Status>(player).water, None);
+    assert_eq!(simulation.get::<GravityScale>(player).0, 1.0);
+
+    simulation.step(120);
+
+    let status = simulation.get::<Status>(player);
+
+    assert_eq!(status.water, Some(water));
+    assert!(status.surface.is_none());
+    assert_eq!(simulation.get::<GravityScale>(player).0, 0.0);
+
+    let height = simulation.get::<Transform>(player).translation.y;
+
+    assert!(height < 0.0, "player is at {}", height);
+    assert!(height > -10.0, "player sank to {}", height);
+}
+
+#[test]
+fn buoyancy_decides_whether_player_floats_or_sinks() {
+    let mut simulation = Simulation::new();
+
+    let (floater, _) = swimmer(&mut simulation, 2.0);
+
+    let mut other = Simulation::new();
+
+    let (sinker, _) = swimmer(&mut other, 0.0);
+
+    simulation.step(60);
+    other.step(60);
+
+    let floated = simulation.get::<Transform>(floater).translation.y;
+    let sank = other.get::<Transform>(sinker).translation.y;
+
+    assert!(floated > -9.5, "floating player is at {}", floated);
+    assert!(sank < -10.5, "sinking player is at {}", sank);
+}
+
+#[test]
+fn neutral_buoyancy_holds_depth() {
+    let mut simulation = Simulation::new();
+
+    let (player, _) = swimmer(&mut simulation, 1.0);
+
+    simulation.step(60);
+
+    let height = simulation.get::<Transform>(player).translation.y;
+
+    assert!((height + 10.0).abs() < 0.5, "player drifted to {}", height);
+}
+
+#[test]
+fn player_swims_forward_and_up() {
+    let mut simulation = Simulation::new();
+
+    let (player, _) = swimmer(&mut simulation, 1.0);
+
+    let mut input = simulation.world_mut().get_mut::<Input>(player).unwrap();
+
+    input.moving = Vec2::NEG_Y;
+    input.swimming_up = true;
+
+    simulation.step(60);
+
+    let translation = simulation.get::<Transform>(player).translation;
+
+    assert!(translation.z < -1.0, "player swam to {}", translation);
+    assert!(translation.y > -9.0, "player swam to {}", translation);
+
+    let speed = simulation.get::<Velocity>(player).linvel.length();
+
+    let swimming_speed = simulation.get::<Parameters>(player).swimming_speed;
+
+    assert!(speed <= swimming_speed + 0.01, "player swims at {}", speed);
+}
+
+#[test]
+fn player_swims_down() {
+    let mut simulation = Simulation::new();
+
+    let (player, _) = swimmer(&mut simulation, 1.0);
+
+    simulation
+        .world_mut()
+        .get_mut::<Input>(player)
+        .unwrap()
+        .swimming_down = true;
+
+    simulation.step(60);
+
+    let height = simulation.get::<Transform>(player).translation.y;
+
+    assert!(height < -11.0, "player swam down to {}", height);
+}
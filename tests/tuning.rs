@@ -0,0 +1,170 @@
+use std::{env, thread, time::Duration};
+
+use bevy::{asset::LoadState, prelude::*};
+use bevy_rapier3d::prelude::Collider;
+use rust_game::{
+    entities::player::{DefaultTuning, LocalPlayer, Parameters, Player, PlayerTuning, Tuning},
+    headless::Simulation,
+    save,
+};
+
+fn wait_for_default(simulation: &mut Simulation) -> Handle<PlayerTuning> {
+    let handle = simulation.world().resource::<DefaultTuning>().0.clone();
+
+    for _ in 0..500 {
+        if simulation
+            .world()
+            .resource::<AssetServer>()
+            .load_state(&handle)
+            .is_loaded()
+        {
+            break;
+        }
+
+        thread::sleep(Duration::from_millis(1));
+
+        simulation.step(1);
+    }
+
+    let state = simulation
+        .world()
+        .resource::<AssetServer>()
+        .load_state(&handle);
+
+    assert!(matches!(state, LoadState::Loaded), "tuning is {:?}", state);
+
+    handle
+}
+
+#[test]
+fn default_tuning_matches_parameters() {
+    let mut simulation = Simulation::new();
+
+    let handle = wait_for_default(&mut simulation);
+
+    let tuning = simulation
+        .world()
+        .resource::<Assets<PlayerTuning>>()
+        .get(&handle)
+        .unwrap();
+
+    assert!(tuning.0 == Parameters::default());
+}
+
+#[test]
+fn default_tuning_is_only_applied_to_local_players() {
+    let mut simulation = Simulation::new();
+
+    let handle = wait_for_default(&mut simulation);
+
+    let player = simulation.spawn(&Player);
+    let local = simulation.spawn(&LocalPlayer);
+
+    simulation
+        .world_mut()
+        .get_mut::<Parameters>(player)
+        .unwrap()
+        .walking_speed = 6.0;
+
+    simulation
+        .world_mut()
+        .resource_mut::<Assets<PlayerTuning>>()
+        .get_mut(&handle)
+        .unwrap()
+        .0
+        .running_speed = 10.0;
+
+    simulation.step(2);
+
+    assert!(simulation.world().get::<Tuning>(player).is_none());
+    assert_eq!(simulation.get::<Parameters>(player).walking_speed, 6.0);
+    assert_eq!(simulation.get::<Parameters>(player).running_speed, 8.0);
+
+    assert_eq!(*simulation.get::<Tuning>(local).handle(), handle);
+    assert_eq!(simulation.get::<Parameters>(local).running_speed, 10.0);
+}
+
+#[test]
+fn tuning_is_applied_to_existing_players() {
+    let mut simulation = Simulation::new();
+
+    let player = simulation.spawn(&Player);
+
+    simulation.step(10);
+
+    let handle = simulation
+        .world_mut()
+        .resource_mut::<Assets<PlayerTuning>>()
+        .add(PlayerTuning(Parameters {
+            walking_speed: 6.0,
+            ..default()
+        }));
+
+    simulation
+        .world_mut()
+        .entity_mut(player)
+        .insert(Tuning::new(handle.clone()));
+
+    simulation.step(2);
+
+    assert_eq!(simulation.get::<Parameters>(player).walking_speed, 6.0);
+
+    simulation
+        .world_mut()
+        .resource_mut::<Assets<PlayerTuning>>()
+        .get_mut(&handle)
+        .unwrap()
+        .0
+        .collider_radius = 0.4;
+
+    simulation.step(4);
+
+    assert_eq!(simulation.get::<Parameters>(player).collider_radius, 0.4);
+
+    let radius = simulation
+        .get::<Collider>(player)
+        .as_capsule()
+        .unwrap()
+        .radius();
+
+    assert_eq!(radius, 0.4);
+}
+
+#[test]
+fn tuning_is_applied_after_quickload() {
+    let mut simulation = Simulation::new();
+
+    let handle = wait_for_default(&mut simulation);
+
+    simulation.spawn(&LocalPlayer);
+
+    simulation.step(2);
+
+    let path = env::temp_dir().join("rust_game_tuning_is_applied_after_quickload.ron");
+
+    save::save(simulation.world_mut(), &path).unwrap();
+
+    save::load(simulation.world_mut(), &path).unwrap();
+
+    simulation.step(2);
+
+    let world = simulation.world_mut();
+
+    let player = world
+        .query_filtered::<Entity, With<Parameters>>()
+        .single(world);
+
+    assert_eq!(*simulation.get::<Tuning>(player).handle(), handle);
+
+    simulation
+        .world_mut()
+        .resource_mut::<Assets<PlayerTuning>>()
+        .get_mut(&handle)
+        .unwrap()
+        .0
+        .walking_speed = 5.0;
+
+    simulation.step(2);
+
+    assert_eq!(simulation.get::<Parameters>(player).walking_speed, 5.0);
+}